@@ -3,6 +3,8 @@ extern crate genetics;
 extern crate simplelog;
 #[macro_use]
 extern crate log;
+#[macro_use]
+extern crate serde_derive;
 
 
 mod cidade;
@@ -18,12 +20,14 @@ use genetics::observador::ObservadorEvolucaoImprimeAptidao;
 use genetics::genetico::Genetico;
 use genetics::populacao::{Populacao, CriadorIndividuos, Operacao};
 
-use std::rc::Rc;
+use std::sync::Arc;
 use std::collections::HashMap;
+use std::env;
+use std::path::Path;
 
 use simplelog::{TermLogger, CombinedLogger, LogLevelFilter};
 
-#[derive(Clone,Debug)]
+#[derive(Clone,Debug,Serialize,Deserialize)]
 struct Rota {
 	alterada: bool,
 	cidades: Vec<usize>,
@@ -35,38 +39,56 @@ fn main() {
 
     debug!("Iniciando");
     let problema = cidade::ler("/home/diego/projects/genetics/cvrp/dados/problemas/A-n32-k5.vrp");
-    let problema = Rc::new(problema);
+    let problema = Arc::new(problema);
 
     let aptidao = AptidaoRota { problema: problema.clone() };
     let criador_rotas = CriadorRotas { problema: problema.clone() };
     let cruzador_rotas = CruzadorRotas { problema: problema.clone() };
 
-    let populacao = Populacao::criar(aptidao, &criador_rotas, 100, Operacao::Min);
+    let mut populacao = Populacao::criar(aptidao, &criador_rotas, 100, Operacao::Min);
+    populacao.avaliacao_paralela(4);
     let mut evolucao = EvolucaoMista::criar();
-    evolucao.adicionar(Cruzamento::criar(SelecaoPorTorneio::criar(5), cruzador_rotas, 0.9));
+    let torneio = SelecaoPorTorneio::criar(5).unwrap();
+    evolucao.adicionar(Cruzamento::criar(torneio, cruzador_rotas, 0.9));
 
     let objetivo = NumeroMaximoIteracoes::criar(1000);
 
     let mut genetico = Genetico::criar(populacao, evolucao, objetivo);
     genetico.observador(ObservadorEvolucaoImprimeAptidao);
 
-    let solucao = genetico.buscar_solucao();
+    // Com '--checkpoint <arquivo>' o estado é salvo no arquivo a cada 50 gerações e, quando o
+    // arquivo já existe, a execução continua do ponto em que foi salva
+    if let Some(checkpoint) = arquivo_checkpoint() {
+        if Path::new(&checkpoint).exists() {
+            genetico.restaurar(&checkpoint)
+                    .unwrap_or_else(|erro| panic!("Falha ao ler {}: {}", checkpoint, erro));
+        }
+        genetico.salvar_a_cada(50, &checkpoint);
+    }
+
+    let solucao = genetico.buscar_solucao().melhor.genes;
     println!("Solucao:");
     for rota in solucao {
 	    println!("\t{:?}", rota.cidades);	
     }
 }
 
+/// O arquivo informado depois de '--checkpoint' na linha de comando.
+fn arquivo_checkpoint() -> Option<String> {
+    let mut args = env::args().skip_while(|arg| arg != "--checkpoint");
+    args.next().map(|_| args.next().expect("Informe o arquivo depois de --checkpoint"))
+}
+
 struct CriadorRotas {
-    problema: Rc<Problema>,
+    problema: Arc<Problema>,
 }
 
 struct AptidaoRota {
-    problema: Rc<Problema>,
+    problema: Arc<Problema>,
 }
 
 struct CruzadorRotas {
-    problema: Rc<Problema>,
+    problema: Arc<Problema>,
 }
 
 impl Cruzador<Vec<Rota>> for CruzadorRotas {
@@ -555,7 +577,7 @@ fn deve_criar_cidades() {
 
     // Criando uma rota aleatoria
     let mut aleatorio = Aleatorio::criar();
-    let criador_rotas = CriadorRotas { problema: Rc::new(problema) };
+    let criador_rotas = CriadorRotas { problema: Arc::new(problema) };
     let possivel_rota = criador_rotas.criar(&mut aleatorio);
 
     // Imprimindo a rota para debug
@@ -607,7 +629,7 @@ fn deve_calcular_aptidao() {
 		],
     };
 
-    let aptidao_rota = AptidaoRota { problema: Rc::new(problema) };
+    let aptidao_rota = AptidaoRota { problema: Arc::new(problema) };
     let aptidao = aptidao_rota.calcular_aptidao(&rotas);
     assert_eq!(aptidao, 104.41992714635366);
 
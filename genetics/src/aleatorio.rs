@@ -1,29 +1,84 @@
 
-use rand::{thread_rng, Rng, ThreadRng};
+use rand::{thread_rng, Rng};
 use rand::distributions::range::SampleRange;
 use std::fmt::Debug;
 
+/// Gerador de números pseudo-aleatórios xorshift128+. O estado é pequeno e conhecido, o que
+/// permite reproduzir uma execução inteira a partir de uma semente.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Gerador {
+    estado: [u64; 2],
+}
+
+impl Gerador {
+    /// Cria um gerador a partir de uma semente. A semente é espalhada com splitmix64 para que
+    /// sementes próximas não gerem sequências parecidas.
+    pub fn semente(semente: u64) -> Self {
+        let mut s = semente;
+        let primeiro = splitmix64(&mut s);
+        let segundo = splitmix64(&mut s);
+        Gerador { estado: [primeiro, segundo | 1] }
+    }
+}
+
+impl Rng for Gerador {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut s1 = self.estado[0];
+        let s0 = self.estado[1];
+        self.estado[0] = s0;
+        s1 ^= s1 << 23;
+        self.estado[1] = s1 ^ s0 ^ (s1 >> 17) ^ (s0 >> 26);
+        self.estado[1].wrapping_add(s0)
+    }
+}
+
+fn splitmix64(estado: &mut u64) -> u64 {
+    *estado = estado.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *estado;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Aleatorio {
-    rand: ThreadRng,
+    rand: Gerador,
     vicio: Option<f64>,
 }
 
 impl Aleatorio {
+    /// Cria um gerador com uma semente qualquer, tirada do gerador da thread.
     pub fn criar() -> Self {
+        Aleatorio::semente(thread_rng().gen())
+    }
+
+    /// Cria um gerador que sempre produz a mesma sequência para a mesma semente.
+    pub fn semente(semente: u64) -> Self {
         Aleatorio {
-            rand: thread_rng(),
+            rand: Gerador::semente(semente),
             vicio: None,
         }
     }
 
     pub fn viciado(valor: f64) -> Self {
         Aleatorio {
-            rand: thread_rng(),
+            rand: Gerador::semente(thread_rng().gen()),
             vicio: Some(valor),
         }
     }
 
 
+    /// Cria um novo gerador a partir deste. A sequência do gerador criado depende apenas do
+    /// estado deste gerador, o que permite distribuir o trabalho entre threads sem perder a
+    /// reprodutibilidade.
+    pub fn derivar(&mut self) -> Aleatorio {
+        Aleatorio::semente(self.rand.gen())
+    }
+
     pub fn intervalo<T: PartialOrd + SampleRange + Debug>(&mut self, inicio: T, fim: T) -> T {
         self.rand.gen_range(inicio, fim)
     }
@@ -31,4 +86,47 @@ impl Aleatorio {
     pub fn chance(&mut self) -> f64 {
         self.vicio.unwrap_or_else(|| self.rand.gen())
     }
+
+    /// Sorteia um valor da distribuição normal com média 0 e desvio padrão 1 (Box-Muller).
+    pub fn normal(&mut self) -> f64 {
+        let u1: f64 = 1.0 - self.rand.gen::<f64>();
+        let u2: f64 = self.rand.gen();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * ::std::f64::consts::PI * u2).cos()
+    }
+
+    /// Embaralha os elementos de *valores* (Fisher-Yates).
+    pub fn embaralhar<T>(&mut self, valores: &mut [T]) {
+        for i in (1..valores.len()).rev() {
+            let j = self.intervalo(0, i + 1);
+            valores.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn mesma_semente_deve_gerar_mesma_sequencia() {
+        let mut a = Aleatorio::semente(42);
+        let mut b = Aleatorio::semente(42);
+
+        for _ in 0..100 {
+            assert_eq!(a.chance(), b.chance());
+            assert_eq!(a.intervalo(0, 1000), b.intervalo(0, 1000));
+        }
+    }
+
+    #[test]
+    fn sementes_diferentes_devem_gerar_sequencias_diferentes() {
+        let mut a = Aleatorio::semente(1);
+        let mut b = Aleatorio::semente(2);
+
+        let seq_a: Vec<usize> = (0..10).map(|_| a.intervalo(0, 1000)).collect();
+        let seq_b: Vec<usize> = (0..10).map(|_| b.intervalo(0, 1000)).collect();
+
+        assert!(seq_a != seq_b);
+    }
 }
@@ -1,9 +1,39 @@
 
+use crossbeam;
 
 pub trait Aptidao<Gene> {
     fn calcular_aptidao(&self, genes: &Gene) -> f64;
 }
 
+/// Calcula a aptidão de um lote de genes dividindo o trabalho entre *threads* threads. O
+/// resultado fica na mesma ordem dos genes e é igual ao do cálculo sequencial.
+pub fn calcular_aptidoes<Gene>(aptidao: &(dyn Aptidao<Gene> + Send + Sync),
+                               genes: &[Gene],
+                               threads: usize)
+                               -> Vec<f64>
+    where Gene: Sync
+{
+    if threads <= 1 || genes.len() < 2 {
+        return genes.iter().map(|g| aptidao.calcular_aptidao(g)).collect();
+    }
+
+    trace!("Calculando aptidão de {} genes em {} threads", genes.len(), threads);
+    let tamanho = genes.len().div_ceil(threads);
+    let mut aptidoes = vec![0.0; genes.len()];
+    crossbeam::scope(|escopo| {
+        for (pedaco, resultado) in genes.chunks(tamanho).zip(aptidoes.chunks_mut(tamanho)) {
+            escopo.spawn(move |_| {
+                for (gene, apt) in pedaco.iter().zip(resultado.iter_mut()) {
+                    *apt = aptidao.calcular_aptidao(gene);
+                }
+            });
+        }
+    })
+        .expect("Falha no cálculo paralelo da aptidão");
+
+    aptidoes
+}
+
 
 /// Aptidão simples usada para testes. Nessa aptidao o próprio gene é o valor da aptidão.
 #[derive(Clone)]
@@ -14,3 +44,21 @@ impl Aptidao<usize> for AptidaoSimples {
         *genes as f64
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn calculo_paralelo_deve_ser_igual_ao_sequencial() {
+        let genes: Vec<usize> = (0..103).collect();
+
+        let sequencial = calcular_aptidoes(&AptidaoSimples, &genes, 1);
+        let paralelo = calcular_aptidoes(&AptidaoSimples, &genes, 4);
+
+        assert_eq!(sequencial, paralelo);
+        assert_eq!(103, paralelo.len());
+        assert_eq!(102.0, paralelo[102]);
+    }
+}
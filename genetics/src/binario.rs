@@ -0,0 +1,310 @@
+//! # Cadeias de bits
+//!
+//! Genoma binário de tamanho qualquer, guardado em palavras de 64 bits. Os problemas com
+//! codificação binária normalmente dividem a cadeia em segmentos, cada um representando uma
+//! variável. O `Decodificador` converte esses segmentos em números reais ou inteiros, usando a
+//! codificação binária padrão ou o código Gray.
+
+use aleatorio::Aleatorio;
+use populacao::CriadorIndividuos;
+
+const BITS_PALAVRA: usize = 64;
+
+/// Cadeia de bits de tamanho fixo. Os bits que sobram na última palavra ficam sempre zerados,
+/// assim duas cadeias com os mesmos bits são sempre iguais.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CadeiaBits {
+    palavras: Vec<u64>,
+    tamanho: usize,
+}
+
+impl CadeiaBits {
+    /// Cria uma cadeia com *tamanho* bits zerados.
+    pub fn criar(tamanho: usize) -> Self {
+        CadeiaBits {
+            palavras: vec![0; tamanho.div_ceil(BITS_PALAVRA)],
+            tamanho,
+        }
+    }
+
+    /// Cria uma cadeia com *tamanho* bits sorteados.
+    pub fn aleatoria(tamanho: usize, aleatorio: &mut Aleatorio) -> Self {
+        let mut cadeia = CadeiaBits::criar(tamanho);
+        for palavra in cadeia.palavras.iter_mut() {
+            *palavra = aleatorio.intervalo(0, u64::MAX);
+        }
+        cadeia.zerar_sobra();
+        cadeia
+    }
+
+    /// Cria uma cadeia com os bits informados, na mesma ordem.
+    pub fn de_bits(bits: &[bool]) -> Self {
+        let mut cadeia = CadeiaBits::criar(bits.len());
+        for (posicao, bit) in bits.iter().enumerate() {
+            cadeia.definir(posicao, *bit);
+        }
+        cadeia
+    }
+
+    /// Quantidade de bits da cadeia.
+    pub fn len(&self) -> usize {
+        self.tamanho
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tamanho == 0
+    }
+
+    /// As palavras onde os bits estão guardados, o bit *i* é o bit *i* % 64 da palavra *i* / 64.
+    pub fn palavras(&self) -> &[u64] {
+        &self.palavras
+    }
+
+    pub fn bit(&self, posicao: usize) -> bool {
+        assert!(posicao < self.tamanho, "Bit {} fora da cadeia de {}", posicao, self.tamanho);
+        self.palavras[posicao / BITS_PALAVRA] & (1 << (posicao % BITS_PALAVRA)) != 0
+    }
+
+    pub fn definir(&mut self, posicao: usize, valor: bool) {
+        if self.bit(posicao) != valor {
+            self.inverter(posicao);
+        }
+    }
+
+    pub fn inverter(&mut self, posicao: usize) {
+        assert!(posicao < self.tamanho, "Bit {} fora da cadeia de {}", posicao, self.tamanho);
+        self.palavras[posicao / BITS_PALAVRA] ^= 1 << (posicao % BITS_PALAVRA);
+    }
+
+    /// Os bits da cadeia em ordem.
+    pub fn bits<'a>(&'a self) -> impl Iterator<Item = bool> + 'a {
+        (0..self.tamanho).map(move |posicao| self.bit(posicao))
+    }
+
+    /// Quantidade de bits ligados.
+    pub fn uns(&self) -> usize {
+        self.palavras.iter().map(|palavra| palavra.count_ones() as usize).sum()
+    }
+
+    /// Lê os *largura* bits a partir de *inicio* como um número sem sinal, o primeiro bit é o
+    /// mais significativo.
+    pub fn segmento(&self, inicio: usize, largura: usize) -> u64 {
+        assert!(largura <= 64, "Segmento com mais de 64 bits");
+        (inicio..inicio + largura).fold(0, |valor, posicao| (valor << 1) | self.bit(posicao) as u64)
+    }
+
+    /// Grava *valor* nos *largura* bits a partir de *inicio*, na mesma ordem de `segmento`.
+    pub fn definir_segmento(&mut self, inicio: usize, largura: usize, valor: u64) {
+        assert!(largura <= 64, "Segmento com mais de 64 bits");
+        for i in 0..largura {
+            self.definir(inicio + i, valor & (1 << (largura - 1 - i)) != 0);
+        }
+    }
+
+    /// Gera duas cadeias trocando entre esta e a *outra* os bits ligados na *mascara*. As
+    /// cadeias devem ter o mesmo tamanho.
+    pub fn trocar_bits(&self,
+                       outra: &CadeiaBits,
+                       mascara: &CadeiaBits)
+                       -> (CadeiaBits, CadeiaBits) {
+        let mut primeira = self.clone();
+        let mut segunda = outra.clone();
+        for ((a, b), m) in primeira.palavras
+                                   .iter_mut()
+                                   .zip(segunda.palavras.iter_mut())
+                                   .zip(mascara.palavras.iter()) {
+            let diferenca = (*a ^ *b) & m;
+            *a ^= diferenca;
+            *b ^= diferenca;
+        }
+        (primeira, segunda)
+    }
+
+    fn zerar_sobra(&mut self) {
+        let sobra = self.tamanho % BITS_PALAVRA;
+        if sobra > 0 {
+            if let Some(ultima) = self.palavras.last_mut() {
+                *ultima &= (1 << sobra) - 1;
+            }
+        }
+    }
+}
+
+/// Cria indivíduos com cadeias de *tamanho* bits sorteados.
+pub struct CriadorBits {
+    pub tamanho: usize,
+}
+
+impl CriadorIndividuos<CadeiaBits> for CriadorBits {
+    fn criar(&self, aleatorio: &mut Aleatorio) -> CadeiaBits {
+        CadeiaBits::aleatoria(self.tamanho, aleatorio)
+    }
+}
+
+/// Como os números são escritos nos segmentos.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Codificacao {
+    /// Binário padrão
+    Binaria,
+    /// Código Gray: números vizinhos diferem em um único bit, o que evita que uma pequena
+    /// mudança no valor precise de várias mutações
+    Gray,
+}
+
+/// Segmento de *bits* bits que representa uma variável entre *minimo* e *maximo*.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segmento {
+    pub bits: usize,
+    pub minimo: f64,
+    pub maximo: f64,
+}
+
+/// Converte os segmentos de uma cadeia de bits em variáveis. Os segmentos são lidos em ordem a
+/// partir do primeiro bit, o menor número do segmento vira o *minimo* e o maior o *maximo*.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decodificador {
+    pub segmentos: Vec<Segmento>,
+    pub codificacao: Codificacao,
+}
+
+impl Decodificador {
+    pub fn criar(codificacao: Codificacao) -> Self {
+        Decodificador {
+            segmentos: Vec::new(),
+            codificacao,
+        }
+    }
+
+    /// Cria um decodificador com *quantidade* segmentos iguais.
+    pub fn uniforme(quantidade: usize,
+                    bits: usize,
+                    minimo: f64,
+                    maximo: f64,
+                    codificacao: Codificacao)
+                    -> Self {
+        let mut decodificador = Decodificador::criar(codificacao);
+        for _ in 0..quantidade {
+            decodificador.adicionar(bits, minimo, maximo);
+        }
+        decodificador
+    }
+
+    /// Adiciona um segmento de 1 a 64 bits.
+    pub fn adicionar(&mut self, bits: usize, minimo: f64, maximo: f64) {
+        assert!(bits > 0 && bits <= 64, "Segmento deve ter entre 1 e 64 bits");
+        self.segmentos.push(Segmento {
+            bits,
+            minimo,
+            maximo,
+        });
+    }
+
+    /// Quantidade de bits usados por todos os segmentos.
+    pub fn tamanho(&self) -> usize {
+        self.segmentos.iter().map(|segmento| segmento.bits).sum()
+    }
+
+    /// Valores reais de cada segmento.
+    pub fn reais(&self, genes: &CadeiaBits) -> Vec<f64> {
+        let mut inicio = 0;
+        self.segmentos
+            .iter()
+            .map(|segmento| {
+                let mut valor = genes.segmento(inicio, segmento.bits);
+                if self.codificacao == Codificacao::Gray {
+                    valor = gray_para_binario(valor);
+                }
+                inicio += segmento.bits;
+                let fracao = valor as f64 / maior_valor(segmento.bits) as f64;
+                segmento.minimo + fracao * (segmento.maximo - segmento.minimo)
+            })
+            .collect()
+    }
+
+    /// Valores de cada segmento arredondados para o inteiro mais próximo. Com 2^*bits* inteiros
+    /// no intervalo cada número do segmento corresponde a um inteiro.
+    pub fn inteiros(&self, genes: &CadeiaBits) -> Vec<i64> {
+        self.reais(genes).into_iter().map(|valor| valor.round() as i64).collect()
+    }
+
+    /// Cadeia de bits com os valores mais próximos possíveis de *valores*.
+    pub fn codificar(&self, valores: &[f64]) -> CadeiaBits {
+        let mut genes = CadeiaBits::criar(self.tamanho());
+        let mut inicio = 0;
+        for (segmento, valor) in self.segmentos.iter().zip(valores.iter()) {
+            let maior = maior_valor(segmento.bits);
+            let fracao = ((valor - segmento.minimo) / (segmento.maximo - segmento.minimo))
+                             .clamp(0.0, 1.0);
+            let mut numero = (fracao * maior as f64).round() as u64;
+            if self.codificacao == Codificacao::Gray {
+                numero ^= numero >> 1;
+            }
+            genes.definir_segmento(inicio, segmento.bits, numero);
+            inicio += segmento.bits;
+        }
+        genes
+    }
+}
+
+fn maior_valor(bits: usize) -> u64 {
+    u64::MAX >> (64 - bits)
+}
+
+fn gray_para_binario(gray: u64) -> u64 {
+    let mut binario = gray;
+    let mut deslocamento = 1;
+    while deslocamento < 64 {
+        binario ^= binario >> deslocamento;
+        deslocamento <<= 1;
+    }
+    binario
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn deve_ler_e_gravar_bits_entre_palavras() {
+        let mut cadeia = CadeiaBits::criar(130);
+        cadeia.definir_segmento(60, 10, 0b10_1100_0111);
+        assert_eq!(0b10_1100_0111, cadeia.segmento(60, 10));
+        assert!(cadeia.bit(60) && !cadeia.bit(61) && cadeia.bit(69));
+        assert_eq!(6, cadeia.uns());
+
+        cadeia.inverter(129);
+        assert_eq!(1 << 1, cadeia.palavras()[2]);
+        assert_eq!(cadeia, CadeiaBits::de_bits(&cadeia.bits().collect::<Vec<bool>>()));
+
+        let mut aleatorio = Aleatorio::semente(47);
+        let a = CadeiaBits::aleatoria(100, &mut aleatorio);
+        let b = CadeiaBits::aleatoria(100, &mut aleatorio);
+        assert!(a.palavras()[1] >> 36 == 0);
+        let (f1, f2) = a.trocar_bits(&b, &CadeiaBits::de_bits(&[true; 100]));
+        assert_eq!((b.clone(), a.clone()), (f1, f2));
+    }
+
+    #[test]
+    fn deve_decodificar_segmentos() {
+        let mut decodificador = Decodificador::criar(Codificacao::Binaria);
+        decodificador.adicionar(10, -500.0, 500.0);
+        decodificador.adicionar(3, 0.0, 7.0);
+        assert_eq!(13, decodificador.tamanho());
+
+        let mut genes = CadeiaBits::criar(13);
+        genes.definir_segmento(0, 10, 1023);
+        genes.definir_segmento(10, 3, 0b101);
+        assert_eq!(vec![500.0, 5.0], decodificador.reais(&genes));
+        assert_eq!(vec![500, 5], decodificador.inteiros(&genes));
+
+        // No Gray 0b111 é o número 5
+        let gray = Decodificador::uniforme(2, 3, 0.0, 7.0, Codificacao::Gray);
+        assert_eq!(vec![5, 0], gray.inteiros(&CadeiaBits::de_bits(&[true, true, true,
+                                                                     false, false, false])));
+        for valor in 0..8 {
+            let valores = vec![valor as f64, 7.0 - valor as f64];
+            assert_eq!(valores, gray.reais(&gray.codificar(&valores)));
+        }
+    }
+}
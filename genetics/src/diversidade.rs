@@ -0,0 +1,286 @@
+//! # Diversidade
+//!
+//! Medidas de quão diferentes são os indivíduos de uma população. Uma população com diversidade
+//! próxima de zero convergiu para cópias de um mesmo indivíduo e dificilmente vai melhorar.
+
+use std::collections::HashMap;
+
+use binario::CadeiaBits;
+use populacao::Individuo;
+
+/// Distância entre dois genomas.
+pub trait Distancia<Gene> {
+    fn distancia(&self, a: &Gene, b: &Gene) -> f64;
+}
+
+/// Genoma que pode ser visto como um vetor de números, um por posição. Usado no cálculo do
+/// centróide, da distância euclidiana e da entropia por posição.
+pub trait Posicoes {
+    fn posicoes(&self) -> Vec<f64>;
+}
+
+/// Valor de um gene que pode ser convertido para número.
+pub trait ValorGene: Copy {
+    fn valor(self) -> f64;
+}
+
+macro_rules! valor_gene {
+    ($($tipo:ty),*) => {
+        $(
+            impl ValorGene for $tipo {
+                fn valor(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    }
+}
+
+valor_gene!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl ValorGene for bool {
+    fn valor(self) -> f64 {
+        if self { 1.0 } else { 0.0 }
+    }
+}
+
+impl<T: ValorGene> Posicoes for Vec<T> {
+    fn posicoes(&self) -> Vec<f64> {
+        self.iter().map(|v| v.valor()).collect()
+    }
+}
+
+impl<T: ValorGene, const N: usize> Posicoes for [T; N] {
+    fn posicoes(&self) -> Vec<f64> {
+        self.iter().map(|v| v.valor()).collect()
+    }
+}
+
+impl Posicoes for CadeiaBits {
+    fn posicoes(&self) -> Vec<f64> {
+        self.bits().map(|bit| bit.valor()).collect()
+    }
+}
+
+/// Quantidade de posições diferentes. Para inteiros usados como cadeia de bits conta os bits
+/// diferentes.
+pub struct DistanciaHamming;
+
+impl<T: PartialEq> Distancia<Vec<T>> for DistanciaHamming {
+    fn distancia(&self, a: &Vec<T>, b: &Vec<T>) -> f64 {
+        hamming(a, b)
+    }
+}
+
+impl<T: PartialEq, const N: usize> Distancia<[T; N]> for DistanciaHamming {
+    fn distancia(&self, a: &[T; N], b: &[T; N]) -> f64 {
+        hamming(a, b)
+    }
+}
+
+fn hamming<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    let diferentes = a.iter().zip(b.iter()).filter(|&(x, y)| x != y).count();
+    (diferentes + a.len().max(b.len()) - a.len().min(b.len())) as f64
+}
+
+macro_rules! hamming_bits {
+    ($($tipo:ty),*) => {
+        $(
+            impl Distancia<$tipo> for DistanciaHamming {
+                fn distancia(&self, a: &$tipo, b: &$tipo) -> f64 {
+                    (a ^ b).count_ones() as f64
+                }
+            }
+        )*
+    }
+}
+
+hamming_bits!(u8, u16, u32, u64, usize);
+
+impl Distancia<CadeiaBits> for DistanciaHamming {
+    fn distancia(&self, a: &CadeiaBits, b: &CadeiaBits) -> f64 {
+        let diferentes: u32 = a.palavras()
+                               .iter()
+                               .zip(b.palavras().iter())
+                               .map(|(x, y)| (x ^ y).count_ones())
+                               .sum();
+        (diferentes as usize + a.len().max(b.len()) - a.len().min(b.len())) as f64
+    }
+}
+
+/// Distância euclidiana entre as posições dos genomas.
+pub struct DistanciaEuclidiana;
+
+impl<Gene: Posicoes> Distancia<Gene> for DistanciaEuclidiana {
+    fn distancia(&self, a: &Gene, b: &Gene) -> f64 {
+        euclidiana(&a.posicoes(), &b.posicoes())
+    }
+}
+
+fn euclidiana(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+/// Distância de Kendall tau entre permutações de 0..n: a quantidade de pares de elementos que
+/// estão em ordem diferente nos dois genomas. Quando algum dos genomas não é uma permutação de
+/// 0..n a distância é NaN.
+pub struct DistanciaKendallTau;
+
+impl Distancia<Vec<usize>> for DistanciaKendallTau {
+    fn distancia(&self, a: &Vec<usize>, b: &Vec<usize>) -> f64 {
+        kendall_tau(a, b)
+    }
+}
+
+impl<const N: usize> Distancia<[usize; N]> for DistanciaKendallTau {
+    fn distancia(&self, a: &[usize; N], b: &[usize; N]) -> f64 {
+        kendall_tau(a, b)
+    }
+}
+
+fn kendall_tau(a: &[usize], b: &[usize]) -> f64 {
+    let posicao_b = match posicoes_permutacao(b) {
+        Some(posicao_b) => posicao_b,
+        None => return f64::NAN,
+    };
+    if a.len() != b.len() || posicoes_permutacao(a).is_none() {
+        return f64::NAN;
+    }
+
+    let mut discordantes = 0;
+    for i in 0..a.len() {
+        for j in (i + 1)..a.len() {
+            if posicao_b[a[i]] > posicao_b[a[j]] {
+                discordantes += 1;
+            }
+        }
+    }
+    discordantes as f64
+}
+
+/// A posição de cada valor na *permutacao*, ou `None` quando ela não é uma permutação de 0..n.
+fn posicoes_permutacao(permutacao: &[usize]) -> Option<Vec<usize>> {
+    let mut posicoes = vec![None; permutacao.len()];
+    for (pos, valor) in permutacao.iter().enumerate() {
+        if *valor >= posicoes.len() || posicoes[*valor].is_some() {
+            return None;
+        }
+        posicoes[*valor] = Some(pos);
+    }
+    posicoes.into_iter().collect()
+}
+
+/// Estatísticas de diversidade de uma população.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Diversidade {
+    /// Média da distância entre todos os pares de indivíduos
+    pub distancia_media: f64,
+    /// Média da distância euclidiana dos indivíduos ao centróide, quando o genoma tem posições
+    pub distancia_centroide: Option<f64>,
+    /// Média da entropia de cada posição do genoma, em bits, quando o genoma tem posições
+    pub entropia: Option<f64>,
+}
+
+/// Média da distância entre todos os pares de indivíduos. O custo é quadrático no tamanho da
+/// população.
+pub fn distancia_media<Gene, D>(individuos: &[Individuo<Gene>], distancia: &D) -> f64
+    where D: Distancia<Gene> + ?Sized
+{
+    let qtd = individuos.len();
+    if qtd < 2 {
+        return 0.0;
+    }
+
+    let mut total = 0.0;
+    for i in 0..qtd {
+        for j in (i + 1)..qtd {
+            total += distancia.distancia(&individuos[i].genes, &individuos[j].genes);
+        }
+    }
+    total / (qtd * (qtd - 1) / 2) as f64
+}
+
+/// Média da distância euclidiana dos indivíduos ao centróide da população.
+pub fn distancia_centroide<Gene: Posicoes>(individuos: &[Individuo<Gene>]) -> f64 {
+    if individuos.is_empty() {
+        return 0.0;
+    }
+
+    let posicoes: Vec<Vec<f64>> = individuos.iter().map(|ind| ind.genes.posicoes()).collect();
+    let mut centroide = vec![0.0; posicoes[0].len()];
+    for pos in posicoes.iter() {
+        for (c, v) in centroide.iter_mut().zip(pos.iter()) {
+            *c += v;
+        }
+    }
+    for c in centroide.iter_mut() {
+        *c /= posicoes.len() as f64;
+    }
+
+    posicoes.iter().map(|pos| euclidiana(pos, &centroide)).sum::<f64>() / posicoes.len() as f64
+}
+
+/// Média, entre as posições do genoma, da entropia de Shannon dos valores encontrados em cada
+/// posição. É zero quando todos os indivíduos são iguais. Faz sentido para genes discretos.
+pub fn entropia_genes<Gene: Posicoes>(individuos: &[Individuo<Gene>]) -> f64 {
+    if individuos.is_empty() {
+        return 0.0;
+    }
+
+    let posicoes: Vec<Vec<f64>> = individuos.iter().map(|ind| ind.genes.posicoes()).collect();
+    let qtd_posicoes = posicoes.iter().map(|pos| pos.len()).min().unwrap_or(0);
+    if qtd_posicoes == 0 {
+        return 0.0;
+    }
+
+    let qtd = posicoes.len() as f64;
+    let mut total = 0.0;
+    for p in 0..qtd_posicoes {
+        let mut frequencias: HashMap<u64, usize> = HashMap::new();
+        for pos in posicoes.iter() {
+            *frequencias.entry(pos[p].to_bits()).or_insert(0) += 1;
+        }
+        total -= frequencias.values()
+                            .map(|f| *f as f64 / qtd)
+                            .map(|prob| prob * prob.log2())
+                            .sum::<f64>();
+    }
+    total / qtd_posicoes as f64
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn individuos<Gene>(genes: Vec<Gene>) -> Vec<Individuo<Gene>> {
+        genes.into_iter().map(|genes| Individuo { genes, aptidao: 0.0 }).collect()
+    }
+
+    #[test]
+    fn deve_calcular_distancias() {
+        assert_eq!(2.0, DistanciaHamming.distancia(&[true, false, true], &[false, false, false]));
+        assert_eq!(2.0, DistanciaHamming.distancia(&0b1010u32, &0b0011u32));
+        assert_eq!(5.0, DistanciaEuclidiana.distancia(&vec![0.0, 0.0], &vec![3.0, 4.0]));
+        assert_eq!(0.0, DistanciaKendallTau.distancia(&[0, 1, 2, 3], &[0, 1, 2, 3]));
+        assert_eq!(1.0, DistanciaKendallTau.distancia(&[0, 1, 2, 3], &[1, 0, 2, 3]));
+        assert_eq!(6.0, DistanciaKendallTau.distancia(&[0, 1, 2, 3], &[3, 2, 1, 0]));
+        assert!(DistanciaKendallTau.distancia(&[0, 1, 2, 5], &[0, 1, 2, 3]).is_nan());
+        assert!(DistanciaKendallTau.distancia(&[0, 1, 2, 3], &[0, 1, 1, 3]).is_nan());
+        assert!(DistanciaKendallTau.distancia(&vec![0, 1, 2], &vec![0, 1, 2, 3]).is_nan());
+    }
+
+    #[test]
+    fn deve_calcular_diversidade() {
+        let iguais = individuos(vec![[1, 0, 1], [1, 0, 1], [1, 0, 1]]);
+        assert_eq!(0.0, distancia_media(&iguais, &DistanciaHamming));
+        assert_eq!(0.0, distancia_centroide(&iguais));
+        assert_eq!(0.0, entropia_genes(&iguais));
+
+        let diferentes = individuos(vec![vec![0, 0], vec![1, 0]]);
+        assert_eq!(1.0, distancia_media(&diferentes, &DistanciaHamming));
+        assert_eq!(0.5, distancia_centroide(&diferentes));
+        // Primeira posição com 1 bit de entropia e a segunda sem nenhuma
+        assert_eq!(0.5, entropia_genes(&diferentes));
+    }
+}
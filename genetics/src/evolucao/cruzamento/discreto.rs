@@ -0,0 +1,202 @@
+//! # Cruzamentos por troca de genes
+//!
+//! Cruzadores que trocam genes inteiros entre os pais, sem alterar os seus valores. Servem para
+//! vetores e arranjos de qualquer tipo de gene e para cadeias de bits, incluindo inteiros usados
+//! como cadeia de bits, onde cada bit é um gene.
+
+use std::mem;
+
+use aleatorio::Aleatorio;
+use binario::CadeiaBits;
+use evolucao::cruzamento::Cruzador;
+
+/// Cruzamento uniforme: cada gene é trocado entre os filhos com a chance informada.
+pub struct CruzadorUniforme {
+    /// Chance, de 0 a 1, de cada gene ser trocado. Com 0.5 cada gene do filho vem de qualquer
+    /// um dos pais com a mesma chance.
+    pub chance_troca: f64,
+}
+
+impl CruzadorUniforme {
+    pub fn criar(chance_troca: f64) -> Self {
+        CruzadorUniforme { chance_troca }
+    }
+
+    fn mascara(&self, qtd: usize, aleatorio: &mut Aleatorio) -> Vec<bool> {
+        (0..qtd).map(|_| aleatorio.chance() < self.chance_troca).collect()
+    }
+}
+
+/// Cruzamento de *k* pontos: o genoma é cortado em *k* posições diferentes e os trechos são
+/// trocados alternadamente entre os filhos.
+pub struct CruzadorPontos {
+    pub pontos: usize,
+}
+
+impl CruzadorPontos {
+    pub fn criar(pontos: usize) -> Self {
+        CruzadorPontos { pontos }
+    }
+
+    fn mascara(&self, qtd: usize, aleatorio: &mut Aleatorio) -> Vec<bool> {
+        // Os cortes ficam entre dois genes, sem repetir posições
+        let mut cortes: Vec<usize> = (1..qtd).collect();
+        aleatorio.embaralhar(&mut cortes);
+        cortes.truncate(self.pontos);
+        cortes.sort();
+
+        let mut mascara = Vec::with_capacity(qtd);
+        let mut trocar = false;
+        let mut proximo = cortes.iter().peekable();
+        for i in 0..qtd {
+            if proximo.peek() == Some(&&i) {
+                proximo.next();
+                trocar = !trocar;
+            }
+            mascara.push(trocar);
+        }
+        mascara
+    }
+}
+
+/// Cruzamento embaralhado: as posições são embaralhadas antes de um cruzamento de um ponto,
+/// eliminando a tendência de genes vizinhos serem herdados juntos.
+pub struct CruzadorEmbaralhado;
+
+impl CruzadorEmbaralhado {
+    fn mascara(&self, qtd: usize, aleatorio: &mut Aleatorio) -> Vec<bool> {
+        let mut posicoes: Vec<usize> = (0..qtd).collect();
+        aleatorio.embaralhar(&mut posicoes);
+        let corte = aleatorio.intervalo(0, qtd + 1);
+
+        let mut mascara = vec![false; qtd];
+        for posicao in posicoes[corte..].iter() {
+            mascara[*posicao] = true;
+        }
+        mascara
+    }
+}
+
+/// Troca entre os filhos os genes das posições marcadas na máscara.
+fn troca<T>(f1: &mut [T], f2: &mut [T], mascara: &[bool]) {
+    for (i, trocar) in mascara.iter().enumerate() {
+        if *trocar {
+            mem::swap(&mut f1[i], &mut f2[i]);
+        }
+    }
+}
+
+macro_rules! cruzador_por_mascara {
+    ($cruzador:ty) => {
+        impl<T: Clone> Cruzador<Vec<T>> for $cruzador {
+            fn cruzar(&self,
+                      aleatorio: &mut Aleatorio,
+                      primeiro: &Vec<T>,
+                      segundo: &Vec<T>)
+                      -> (Vec<T>, Vec<T>) {
+                let mascara = self.mascara(primeiro.len().min(segundo.len()), aleatorio);
+                let mut f1 = primeiro.clone();
+                let mut f2 = segundo.clone();
+                troca(&mut f1, &mut f2, &mascara);
+                (f1, f2)
+            }
+        }
+
+        impl<T: Clone, const N: usize> Cruzador<[T; N]> for $cruzador {
+            fn cruzar(&self,
+                      aleatorio: &mut Aleatorio,
+                      primeiro: &[T; N],
+                      segundo: &[T; N])
+                      -> ([T; N], [T; N]) {
+                let mascara = self.mascara(N, aleatorio);
+                let mut f1 = primeiro.clone();
+                let mut f2 = segundo.clone();
+                troca(&mut f1, &mut f2, &mascara);
+                (f1, f2)
+            }
+        }
+
+        impl Cruzador<CadeiaBits> for $cruzador {
+            fn cruzar(&self,
+                      aleatorio: &mut Aleatorio,
+                      primeiro: &CadeiaBits,
+                      segundo: &CadeiaBits)
+                      -> (CadeiaBits, CadeiaBits) {
+                let mascara = self.mascara(primeiro.len().min(segundo.len()), aleatorio);
+                primeiro.trocar_bits(segundo, &CadeiaBits::de_bits(&mascara))
+            }
+        }
+
+        cruzador_por_mascara!($cruzador; u8, u16, u32, u64, usize);
+    };
+    ($cruzador:ty; $($tipo:ty),*) => {
+        $(
+            impl Cruzador<$tipo> for $cruzador {
+                fn cruzar(&self, aleatorio: &mut Aleatorio, primeiro: &$tipo, segundo: &$tipo)
+                          -> ($tipo, $tipo) {
+                    let bits = mem::size_of::<$tipo>() * 8;
+                    let mascara = self.mascara(bits, aleatorio)
+                                      .into_iter()
+                                      .enumerate()
+                                      .filter(|&(_, trocar)| trocar)
+                                      .fold(0, |mascara: $tipo, (bit, _)| mascara | (1 << bit));
+                    ((primeiro & !mascara) | (segundo & mascara),
+                     (segundo & !mascara) | (primeiro & mascara))
+                }
+            }
+        )*
+    };
+}
+
+cruzador_por_mascara!(CruzadorUniforme);
+cruzador_por_mascara!(CruzadorPontos);
+cruzador_por_mascara!(CruzadorEmbaralhado);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn deve_trocar_genes_entre_os_pais() {
+        let mut aleatorio = Aleatorio::semente(19);
+        let primeiro = [0; 20];
+        let segundo = [1; 20];
+
+        let (f1, f2) = CruzadorUniforme::criar(0.0).cruzar(&mut aleatorio, &primeiro, &segundo);
+        assert_eq!((primeiro, segundo), (f1, f2));
+        let (f1, f2) = CruzadorUniforme::criar(1.0).cruzar(&mut aleatorio, &primeiro, &segundo);
+        assert_eq!((segundo, primeiro), (f1, f2));
+
+        // Com 3 pontos o filho muda de pai exatamente 3 vezes
+        let (f1, f2) = CruzadorPontos::criar(3).cruzar(&mut aleatorio,
+                                                       &primeiro.to_vec(),
+                                                       &segundo.to_vec());
+        assert_eq!(3, f1.windows(2).filter(|par| par[0] != par[1]).count());
+        assert!(f1.iter().zip(f2.iter()).all(|(a, b)| a + b == 1));
+
+        let (f1, f2) = CruzadorEmbaralhado.cruzar(&mut aleatorio, &primeiro, &segundo);
+        assert!(f1.iter().zip(f2.iter()).all(|(a, b)| a + b == 1));
+    }
+
+    #[test]
+    fn deve_cruzar_inteiros_como_bits() {
+        let mut aleatorio = Aleatorio::semente(23);
+        let primeiro: u32 = 0b1100_1010_0110_0011_1100_1010_0110_0011;
+        let segundo: u32 = 0b0101_0011_1010_1100_0101_0011_1010_1100;
+
+        let (f1, f2) = CruzadorPontos::criar(1).cruzar(&mut aleatorio, &primeiro, &segundo);
+        assert_eq!(primeiro ^ segundo, f1 ^ f2);
+        assert_eq!(primeiro & segundo, f1 & f2);
+
+        let (f1, f2) = CruzadorUniforme::criar(1.0).cruzar(&mut aleatorio, &primeiro, &segundo);
+        assert_eq!((segundo, primeiro), (f1, f2));
+
+        let primeiro = CadeiaBits::criar(150);
+        let segundo = CadeiaBits::de_bits(&[true; 150]);
+        let (f1, f2) = CruzadorPontos::criar(2).cruzar(&mut aleatorio, &primeiro, &segundo);
+        assert_eq!(150, f1.uns() + f2.uns());
+        let trocas = f1.bits().zip(f1.bits().skip(1)).filter(|&(a, b)| a != b).count();
+        assert_eq!(2, trocas);
+    }
+}
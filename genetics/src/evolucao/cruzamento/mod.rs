@@ -1,4 +1,14 @@
 
+/// Cruzadores que preservam permutações
+pub mod permutacao;
+/// Cruzadores que trocam genes entre os pais
+pub mod discreto;
+/// Cruzadores para genomas de números reais
+pub mod real;
+
+pub use self::permutacao::*;
+pub use self::discreto::*;
+pub use self::real::*;
 
 use std::vec::Vec;
 use std::marker::PhantomData;
@@ -14,6 +24,15 @@ pub trait Cruzador<Gene> {
     fn cruzar(&self, aleatorio: &mut Aleatorio, primeiro: &Gene, segundo: &Gene) -> (Gene, Gene);
 }
 
+/// Permite escolher o cruzador em tempo de execução.
+impl<Gene, C> Cruzador<Gene> for Box<C>
+    where C: Cruzador<Gene> + ?Sized
+{
+    fn cruzar(&self, aleatorio: &mut Aleatorio, primeiro: &Gene, segundo: &Gene) -> (Gene, Gene) {
+        (**self).cruzar(aleatorio, primeiro, segundo)
+    }
+}
+
 pub struct CruzadorVetor;
 
 
@@ -67,6 +86,7 @@ impl<Gene, Sel, Cruz> Cruzamento<Gene, Sel, Cruz>
     where Cruz: Cruzador<Gene>,
           Sel: Seletor<Gene>
 {
+    #[allow(clippy::redundant_field_names)]
     pub fn criar(seletor: Sel, cruzador: Cruz, chance_cruzamento: f64) -> Self {
         Cruzamento {
             seletor: seletor,
@@ -81,37 +101,32 @@ impl<Gene, Sel, Cruz> Cruzamento<Gene, Sel, Cruz>
 
 impl<Gene, Sel, Cruz> Evolucao<Gene> for Cruzamento<Gene, Sel, Cruz>
     where Cruz: Cruzador<Gene>,
-          Sel: Seletor<Gene>
+          Sel: Seletor<Gene>,
+          Gene: Sync
 {
-    fn evoluir(&self, pop: &mut Populacao<Gene>) -> Populacao<Gene> {
+    fn evoluir(&self, pop: &mut Populacao<Gene>, aleatorio: &mut Aleatorio) -> Populacao<Gene> {
         debug!("Evoluindo por cruzamento");
         let mut nova_geracao = pop.preparar_nova_geracao();
         let qtd = pop.individuos.len();
-        let mut aleatorio = Aleatorio::criar();
-        for idx in 0..qtd {
-
-            if self.chance_cruzamento > aleatorio.chance() {
-                trace!("Cruzando {}/{}", idx, qtd);
-                let par = self.seletor.seleciona(pop);
-                if par.is_some() {
-
-                    let inds = par.unwrap();
-
-                    let (f1, f2) = self.cruzador
-                                       .cruzar(&mut aleatorio, &inds.0.genes, &inds.1.genes);
-
-                    nova_geracao.adicionar(f1);
-                    nova_geracao.adicionar(f2);
-
-
-                    pop.reiterar(inds.0);
-                    pop.reiterar(inds.1);
-
-                }
-            }
+        if qtd < 2 {
+            return nova_geracao;
+        }
 
+        let cruzamentos = (0..qtd).filter(|_| self.chance_cruzamento > aleatorio.chance()).count();
+        let mut filhos = Vec::new();
+
+        // Todos os pais são selecionados de uma vez, sem alterar a população
+        let pares = self.seletor.seleciona_grupos(pop, cruzamentos, 2, aleatorio);
+        for (idx, par) in pares.iter().enumerate() {
+            trace!("Cruzando {}/{}", idx, cruzamentos);
+            let (f1, f2) = self.cruzador.cruzar(aleatorio,
+                                                &pop.individuos[par[0]].genes,
+                                                &pop.individuos[par[1]].genes);
+            filhos.push(f1);
+            filhos.push(f2);
         }
 
+        nova_geracao.adicionar_lote(filhos);
         nova_geracao
     }
 }
@@ -0,0 +1,317 @@
+//! # Cruzamentos de permutações
+//!
+//! Cruzadores para genomas que são permutações, como rotas e ordens de execução. O cruzamento de
+//! um ponto gera filhos com elementos repetidos nesses genomas, os cruzadores daqui sempre geram
+//! permutações dos mesmos elementos dos pais. Os pais devem ter os mesmos elementos, sem
+//! repetições.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use aleatorio::Aleatorio;
+use evolucao::cruzamento::Cruzador;
+
+/// Cruzamento parcialmente mapeado (PMX). Cada filho recebe um trecho de um dos pais, o resto
+/// vem do outro pai e os conflitos são resolvidos pelo mapeamento entre os dois trechos.
+pub struct CruzadorPmx;
+
+impl<T> Cruzador<Vec<T>> for CruzadorPmx
+    where T: Copy + Eq + Hash
+{
+    fn cruzar(&self,
+              aleatorio: &mut Aleatorio,
+              primeiro: &Vec<T>,
+              segundo: &Vec<T>)
+              -> (Vec<T>, Vec<T>) {
+        let (inicio, fim) = pontos_corte(aleatorio, primeiro.len());
+        (pmx(primeiro, segundo, inicio, fim), pmx(segundo, primeiro, inicio, fim))
+    }
+}
+
+fn pmx<T: Copy + Eq + Hash>(trecho: &[T], resto: &[T], inicio: usize, fim: usize) -> Vec<T> {
+    let mapeamento: HashMap<T, usize> = (inicio..fim).map(|i| (trecho[i], i)).collect();
+
+    let mut filho = Vec::with_capacity(trecho.len());
+    for (i, valor) in resto.iter().enumerate() {
+        if i >= inicio && i < fim {
+            filho.push(trecho[i]);
+        } else {
+            let mut valor = *valor;
+            while let Some(&posicao) = mapeamento.get(&valor) {
+                valor = resto[posicao];
+            }
+            filho.push(valor);
+        }
+    }
+    filho
+}
+
+/// Cruzamento de ordem (OX1). Cada filho mantém um trecho de um dos pais nas mesmas posições e
+/// completa o resto na ordem relativa em que os elementos aparecem no outro pai.
+pub struct CruzadorOrdem;
+
+impl<T> Cruzador<Vec<T>> for CruzadorOrdem
+    where T: Copy + Eq + Hash
+{
+    fn cruzar(&self,
+              aleatorio: &mut Aleatorio,
+              primeiro: &Vec<T>,
+              segundo: &Vec<T>)
+              -> (Vec<T>, Vec<T>) {
+        let (inicio, fim) = pontos_corte(aleatorio, primeiro.len());
+        (ordem(primeiro, segundo, inicio, fim), ordem(segundo, primeiro, inicio, fim))
+    }
+}
+
+fn ordem<T: Copy + Eq + Hash>(trecho: &[T], resto: &[T], inicio: usize, fim: usize) -> Vec<T> {
+    let qtd = trecho.len();
+    let mut filho = trecho.to_vec();
+    if qtd == 0 {
+        return filho;
+    }
+
+    let mantidos: HashMap<T, ()> = trecho[inicio..fim].iter().map(|v| (*v, ())).collect();
+
+    // O preenchimento começa depois do trecho e dá a volta no genoma
+    let mut posicao = fim % qtd;
+    for i in 0..qtd {
+        let valor = resto[(fim + i) % qtd];
+        if !mantidos.contains_key(&valor) {
+            filho[posicao] = valor;
+            posicao = (posicao + 1) % qtd;
+        }
+    }
+    filho
+}
+
+/// Cruzamento de ciclo (CX). Os genomas são divididos nos ciclos de posições formados pelos
+/// pais, cada ciclo vem inteiro de um dos pais, alternando entre eles. Todo elemento fica na
+/// mesma posição que tinha em um dos pais.
+pub struct CruzadorCiclo;
+
+impl<T> Cruzador<Vec<T>> for CruzadorCiclo
+    where T: Copy + Eq + Hash
+{
+    fn cruzar(&self,
+              _: &mut Aleatorio,
+              primeiro: &Vec<T>,
+              segundo: &Vec<T>)
+              -> (Vec<T>, Vec<T>) {
+        let posicoes = posicoes(primeiro);
+        let mut f1 = primeiro.clone();
+        let mut f2 = segundo.clone();
+        let mut visitado = vec![false; primeiro.len()];
+
+        // Os ciclos alternam entre manter e trocar os pais
+        let mut trocar = false;
+        for inicio in 0..primeiro.len() {
+            if visitado[inicio] {
+                continue;
+            }
+
+            let mut posicao = inicio;
+            while !visitado[posicao] {
+                visitado[posicao] = true;
+                if trocar {
+                    f1[posicao] = segundo[posicao];
+                    f2[posicao] = primeiro[posicao];
+                }
+                posicao = posicoes[&segundo[posicao]];
+            }
+            trocar = !trocar;
+        }
+
+        (f1, f2)
+    }
+}
+
+/// Cruzamento baseado em posição. Um conjunto aleatório de posições é copiado de um dos pais e
+/// as outras posições recebem os elementos que faltam na ordem em que aparecem no outro pai.
+pub struct CruzadorPosicao;
+
+impl<T> Cruzador<Vec<T>> for CruzadorPosicao
+    where T: Copy + Eq + Hash
+{
+    fn cruzar(&self,
+              aleatorio: &mut Aleatorio,
+              primeiro: &Vec<T>,
+              segundo: &Vec<T>)
+              -> (Vec<T>, Vec<T>) {
+        let selecionadas: Vec<bool> = primeiro.iter().map(|_| aleatorio.chance() < 0.5).collect();
+        (posicao(primeiro, segundo, &selecionadas), posicao(segundo, primeiro, &selecionadas))
+    }
+}
+
+fn posicao<T: Copy + Eq + Hash>(fixo: &[T], resto: &[T], selecionadas: &[bool]) -> Vec<T> {
+    let mantidos: HashMap<T, ()> = fixo.iter()
+                                       .zip(selecionadas.iter())
+                                       .filter(|&(_, selecionada)| *selecionada)
+                                       .map(|(v, _)| (*v, ()))
+                                       .collect();
+
+    let mut restantes = resto.iter().filter(|v| !mantidos.contains_key(v));
+    fixo.iter()
+        .zip(selecionadas.iter())
+        .map(|(v, selecionada)| {
+            if *selecionada {
+                *v
+            } else {
+                *restantes.next().expect("Pais com elementos diferentes")
+            }
+        })
+        .collect()
+}
+
+/// Cruzamento por recombinação de arestas (ERX). Cada filho é montado seguindo as vizinhanças
+/// que existem nos pais, preferindo o vizinho com menos vizinhos restantes. Preserva a maior
+/// parte das arestas dos pais, indicado para rotas.
+pub struct CruzadorArestas;
+
+impl<T> Cruzador<Vec<T>> for CruzadorArestas
+    where T: Copy + Eq + Hash
+{
+    fn cruzar(&self,
+              aleatorio: &mut Aleatorio,
+              primeiro: &Vec<T>,
+              segundo: &Vec<T>)
+              -> (Vec<T>, Vec<T>) {
+        if primeiro.is_empty() {
+            return (primeiro.clone(), segundo.clone());
+        }
+
+        let vizinhos = vizinhancas(primeiro, segundo);
+        let posicoes = posicoes(primeiro);
+        let f1 = recombina_arestas(primeiro, vizinhos.clone(), 0, aleatorio);
+        let f2 = recombina_arestas(primeiro, vizinhos, posicoes[&segundo[0]], aleatorio);
+        (f1, f2)
+    }
+}
+
+/// Vizinhos de cada elemento nos dois pais, identificados pela posição no primeiro pai.
+fn vizinhancas<T: Copy + Eq + Hash>(primeiro: &[T], segundo: &[T]) -> Vec<Vec<usize>> {
+    let qtd = primeiro.len();
+    let posicoes = posicoes(primeiro);
+    let mut vizinhos = vec![Vec::with_capacity(4); qtd];
+
+    for pai in [primeiro, segundo].iter() {
+        for i in 0..qtd {
+            let atual = posicoes[&pai[i]];
+            for vizinho in [pai[(i + qtd - 1) % qtd], pai[(i + 1) % qtd]].iter() {
+                let vizinho = posicoes[vizinho];
+                if vizinho != atual && !vizinhos[atual].contains(&vizinho) {
+                    vizinhos[atual].push(vizinho);
+                }
+            }
+        }
+    }
+    vizinhos
+}
+
+fn recombina_arestas<T: Copy>(elementos: &[T],
+                              mut vizinhos: Vec<Vec<usize>>,
+                              inicio: usize,
+                              aleatorio: &mut Aleatorio)
+                              -> Vec<T> {
+
+    let qtd = elementos.len();
+    let mut restantes: Vec<usize> = (0..qtd).collect();
+    let mut filho = Vec::with_capacity(qtd);
+    let mut atual = inicio;
+
+    loop {
+        filho.push(elementos[atual]);
+        restantes.retain(|e| *e != atual);
+        for lista in vizinhos.iter_mut() {
+            lista.retain(|e| *e != atual);
+        }
+        if restantes.is_empty() {
+            return filho;
+        }
+
+        // O próximo é o vizinho com menos vizinhos, sem vizinhos qualquer restante serve
+        let candidatos = &vizinhos[atual];
+        atual = if candidatos.is_empty() {
+            restantes[aleatorio.intervalo(0, restantes.len())]
+        } else {
+            let menor = candidatos.iter().map(|c| vizinhos[*c].len()).min().unwrap_or(0);
+            let melhores: Vec<usize> = candidatos.iter()
+                                                 .cloned()
+                                                 .filter(|c| vizinhos[*c].len() == menor)
+                                                 .collect();
+            melhores[aleatorio.intervalo(0, melhores.len())]
+        };
+    }
+}
+
+/// Posição de cada elemento no genoma.
+fn posicoes<T: Copy + Eq + Hash>(genes: &[T]) -> HashMap<T, usize> {
+    genes.iter().enumerate().map(|(i, v)| (*v, i)).collect()
+}
+
+/// Sorteia o trecho [inicio, fim) usado nos cruzamentos de dois pontos.
+fn pontos_corte(aleatorio: &mut Aleatorio, qtd: usize) -> (usize, usize) {
+    let a = aleatorio.intervalo(0, qtd + 1);
+    let b = aleatorio.intervalo(0, qtd + 1);
+    (a.min(b), a.max(b))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn valida_permutacao(genes: &[usize]) {
+        let mut ordenados = genes.to_vec();
+        ordenados.sort();
+        assert_eq!((0..genes.len()).collect::<Vec<usize>>(), ordenados);
+    }
+
+    fn embaralhado(qtd: usize, aleatorio: &mut Aleatorio) -> Vec<usize> {
+        let mut genes: Vec<usize> = (0..qtd).collect();
+        aleatorio.embaralhar(&mut genes);
+        genes
+    }
+
+    fn valida_cruzador<C: Cruzador<Vec<usize>>>(cruzador: C) {
+        let mut aleatorio = Aleatorio::semente(13);
+        for qtd in [1, 2, 5, 10, 30].iter() {
+            for _ in 0..50 {
+                let primeiro = embaralhado(*qtd, &mut aleatorio);
+                let segundo = embaralhado(*qtd, &mut aleatorio);
+                let (f1, f2) = cruzador.cruzar(&mut aleatorio, &primeiro, &segundo);
+                valida_permutacao(&f1);
+                valida_permutacao(&f2);
+            }
+        }
+    }
+
+    #[test]
+    fn deve_gerar_permutacoes_validas() {
+        valida_cruzador(CruzadorPmx);
+        valida_cruzador(CruzadorOrdem);
+        valida_cruzador(CruzadorCiclo);
+        valida_cruzador(CruzadorPosicao);
+        valida_cruzador(CruzadorArestas);
+    }
+
+    #[test]
+    fn deve_preservar_posicoes_e_arestas_dos_pais() {
+        let mut aleatorio = Aleatorio::semente(17);
+        let primeiro = vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let segundo = vec![7, 2, 5, 4, 0, 3, 1, 6];
+
+        // No ciclo cada elemento mantém a posição de um dos pais
+        let (f1, f2) = CruzadorCiclo.cruzar(&mut aleatorio, &primeiro, &segundo);
+        for i in 0..primeiro.len() {
+            assert!(f1[i] == primeiro[i] || f1[i] == segundo[i]);
+            assert!(f2[i] == primeiro[i] || f2[i] == segundo[i]);
+        }
+
+        // Com pais iguais o filho só usa arestas dos pais
+        let (filho, _) = CruzadorArestas.cruzar(&mut aleatorio, &primeiro, &primeiro);
+        for par in filho.windows(2) {
+            let distancia = (par[0] as isize - par[1] as isize).abs();
+            assert!(distancia == 1 || distancia == 7);
+        }
+    }
+}
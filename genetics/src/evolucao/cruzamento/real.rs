@@ -0,0 +1,364 @@
+//! # Cruzamentos reais
+//!
+//! Cruzadores para genomas de números reais. Ao contrário dos cruzamentos por troca de genes,
+//! que apenas recombinam os valores dos pais, estes geram valores novos entre ou ao redor dos
+//! valores dos pais. Os genes gerados são mantidos dentro dos limites de cada variável.
+
+use aleatorio::Aleatorio;
+use aptidao::Aptidao;
+use evolucao::cruzamento::Cruzador;
+use evolucao::limites::Limites;
+use evolucao::mutacao::GenomaAutoAdaptativo;
+use populacao::Operacao;
+
+use std::cmp::Ordering;
+
+/// Cruzamento BLX-α: cada gene do filho é sorteado no intervalo formado pelos genes dos pais,
+/// estendido em α vezes a distância entre eles para cada lado.
+pub struct CruzadorBlx {
+    /// Extensão do intervalo, 0.5 é o valor mais usado
+    pub alfa: f64,
+    pub limites: Limites,
+}
+
+impl CruzadorBlx {
+    pub fn criar(alfa: f64, limites: Limites) -> Self {
+        CruzadorBlx { alfa, limites }
+    }
+
+    fn cruzar_reais(&self,
+                    aleatorio: &mut Aleatorio,
+                    primeiro: &[f64],
+                    segundo: &[f64],
+                    f1: &mut [f64],
+                    f2: &mut [f64]) {
+        for i in 0..f1.len() {
+            let menor = primeiro[i].min(segundo[i]);
+            let maior = primeiro[i].max(segundo[i]);
+            let extensao = self.alfa * (maior - menor);
+            let (minimo, maximo) = self.limites.intervalo(i);
+            let inicio = (menor - extensao).max(minimo);
+            let fim = (maior + extensao).min(maximo);
+
+            f1[i] = sorteia(aleatorio, inicio, fim);
+            f2[i] = sorteia(aleatorio, inicio, fim);
+        }
+    }
+}
+
+/// Sorteia um valor em [inicio, fim), aceitando intervalos vazios.
+fn sorteia(aleatorio: &mut Aleatorio, inicio: f64, fim: f64) -> f64 {
+    if fim > inicio {
+        aleatorio.intervalo(inicio, fim)
+    } else {
+        inicio
+    }
+}
+
+/// Cruzamento binário simulado (SBX): os filhos ficam espalhados ao redor dos pais como no
+/// cruzamento de um ponto de cadeias binárias, a média dos filhos é a média dos pais.
+pub struct CruzadorSbx {
+    /// Índice de distribuição η. Valores altos geram filhos próximos dos pais, valores baixos
+    /// geram filhos mais distantes. Normalmente entre 2 e 20
+    pub indice_distribuicao: f64,
+    pub limites: Limites,
+}
+
+impl CruzadorSbx {
+    pub fn criar(indice_distribuicao: f64, limites: Limites) -> Self {
+        CruzadorSbx {
+            indice_distribuicao,
+            limites,
+        }
+    }
+
+    fn cruzar_reais(&self,
+                    aleatorio: &mut Aleatorio,
+                    primeiro: &[f64],
+                    segundo: &[f64],
+                    f1: &mut [f64],
+                    f2: &mut [f64]) {
+        let expoente = 1.0 / (self.indice_distribuicao + 1.0);
+        for i in 0..f1.len() {
+            let u = aleatorio.chance();
+            let beta = if u <= 0.5 {
+                (2.0 * u).powf(expoente)
+            } else {
+                (1.0 / (2.0 * (1.0 - u))).powf(expoente)
+            };
+
+            let (x1, x2) = (primeiro[i], segundo[i]);
+            f1[i] = self.limites.limitar(i, 0.5 * ((1.0 + beta) * x1 + (1.0 - beta) * x2));
+            f2[i] = self.limites.limitar(i, 0.5 * ((1.0 - beta) * x1 + (1.0 + beta) * x2));
+        }
+    }
+}
+
+/// Cruzamento aritmético completo: os filhos são médias ponderadas dos pais,
+/// *peso* · primeiro + (1 - *peso*) · segundo e o inverso. Os filhos sempre ficam entre os pais,
+/// portanto dentro dos limites.
+pub struct CruzadorAritmetico {
+    /// Peso do primeiro pai. Sem peso um novo valor entre 0 e 1 é sorteado em cada cruzamento
+    pub peso: Option<f64>,
+}
+
+impl CruzadorAritmetico {
+    /// Cria um cruzador com peso fixo, com 0.5 os dois filhos são a média dos pais.
+    pub fn criar(peso: f64) -> Self {
+        CruzadorAritmetico { peso: Some(peso) }
+    }
+
+    /// Cria um cruzador que sorteia o peso em cada cruzamento.
+    pub fn criar_aleatorio() -> Self {
+        CruzadorAritmetico { peso: None }
+    }
+
+    fn cruzar_reais(&self,
+                    aleatorio: &mut Aleatorio,
+                    primeiro: &[f64],
+                    segundo: &[f64],
+                    f1: &mut [f64],
+                    f2: &mut [f64]) {
+        let peso = self.peso.unwrap_or_else(|| aleatorio.chance());
+        for i in 0..f1.len() {
+            f1[i] = peso * primeiro[i] + (1.0 - peso) * segundo[i];
+            f2[i] = (1.0 - peso) * primeiro[i] + peso * segundo[i];
+        }
+    }
+}
+
+macro_rules! cruzador_real {
+    ($cruzador:ty) => {
+        impl Cruzador<Vec<f64>> for $cruzador {
+            fn cruzar(&self,
+                      aleatorio: &mut Aleatorio,
+                      primeiro: &Vec<f64>,
+                      segundo: &Vec<f64>)
+                      -> (Vec<f64>, Vec<f64>) {
+                let qtd = primeiro.len().min(segundo.len());
+                let mut f1 = vec![0.0; qtd];
+                let mut f2 = vec![0.0; qtd];
+                self.cruzar_reais(aleatorio, primeiro, segundo, &mut f1, &mut f2);
+                (f1, f2)
+            }
+        }
+
+        impl<const N: usize> Cruzador<[f64; N]> for $cruzador {
+            fn cruzar(&self,
+                      aleatorio: &mut Aleatorio,
+                      primeiro: &[f64; N],
+                      segundo: &[f64; N])
+                      -> ([f64; N], [f64; N]) {
+                let mut f1 = [0.0; N];
+                let mut f2 = [0.0; N];
+                self.cruzar_reais(aleatorio, primeiro, segundo, &mut f1, &mut f2);
+                (f1, f2)
+            }
+        }
+    };
+}
+
+cruzador_real!(CruzadorBlx);
+cruzador_real!(CruzadorSbx);
+cruzador_real!(CruzadorAritmetico);
+
+/// Cruzamento heurístico de Wright: os filhos são gerados a partir do melhor pai, na direção
+/// oposta ao pior pai, melhor + r · (melhor - pior) com r entre 0 e 1. Precisa da aptidão para
+/// saber qual dos pais é o melhor.
+pub struct CruzadorHeuristico<A> {
+    pub aptidao: A,
+    pub operacao: Operacao,
+    pub limites: Limites,
+    /// Quantas vezes um filho fora dos limites é sorteado novamente antes de ser trazido para
+    /// dentro dos limites
+    pub tentativas: usize,
+}
+
+impl<A> CruzadorHeuristico<A> {
+    pub fn criar(aptidao: A, operacao: Operacao, limites: Limites) -> Self {
+        CruzadorHeuristico {
+            aptidao,
+            operacao,
+            limites,
+            tentativas: 3,
+        }
+    }
+
+    fn cruzar_reais(&self,
+                    aleatorio: &mut Aleatorio,
+                    melhor: &[f64],
+                    pior: &[f64],
+                    f1: &mut [f64],
+                    f2: &mut [f64]) {
+        self.heuristico(aleatorio, melhor, pior, f1);
+        self.heuristico(aleatorio, melhor, pior, f2);
+    }
+
+    fn heuristico(&self,
+                  aleatorio: &mut Aleatorio,
+                  melhor: &[f64],
+                  pior: &[f64],
+                  filho: &mut [f64]) {
+        for _ in 0..self.tentativas {
+            let r = aleatorio.chance();
+            for i in 0..filho.len() {
+                filho[i] = melhor[i] + r * (melhor[i] - pior[i]);
+            }
+            if self.dentro_limites(filho) {
+                return;
+            }
+        }
+
+        for (i, valor) in filho.iter_mut().enumerate() {
+            *valor = self.limites.limitar(i, *valor);
+        }
+    }
+
+    fn dentro_limites(&self, genes: &[f64]) -> bool {
+        genes.iter().enumerate().all(|(i, valor)| self.limites.limitar(i, *valor) == *valor)
+    }
+
+    /// Os pais ordenados do melhor para o pior.
+    fn ordenar<'a, G>(&self, primeiro: &'a G, segundo: &'a G) -> (&'a G, &'a G)
+        where A: Aptidao<G>
+    {
+        let aptidao_primeiro = self.aptidao.calcular_aptidao(primeiro);
+        let aptidao_segundo = self.aptidao.calcular_aptidao(segundo);
+        match self.operacao.compara(&aptidao_primeiro, &aptidao_segundo) {
+            Ordering::Less => (segundo, primeiro),
+            _ => (primeiro, segundo),
+        }
+    }
+}
+
+impl<A: Aptidao<Vec<f64>>> Cruzador<Vec<f64>> for CruzadorHeuristico<A> {
+    fn cruzar(&self,
+              aleatorio: &mut Aleatorio,
+              primeiro: &Vec<f64>,
+              segundo: &Vec<f64>)
+              -> (Vec<f64>, Vec<f64>) {
+        let (melhor, pior) = self.ordenar(primeiro, segundo);
+        let qtd = melhor.len().min(pior.len());
+        let mut f1 = vec![0.0; qtd];
+        let mut f2 = vec![0.0; qtd];
+        self.cruzar_reais(aleatorio, melhor, pior, &mut f1, &mut f2);
+        (f1, f2)
+    }
+}
+
+impl<A: Aptidao<[f64; N]>, const N: usize> Cruzador<[f64; N]> for CruzadorHeuristico<A> {
+    fn cruzar(&self,
+              aleatorio: &mut Aleatorio,
+              primeiro: &[f64; N],
+              segundo: &[f64; N])
+              -> ([f64; N], [f64; N]) {
+        let (melhor, pior) = self.ordenar(primeiro, segundo);
+        let mut f1 = [0.0; N];
+        let mut f2 = [0.0; N];
+        self.cruzar_reais(aleatorio, melhor, pior, &mut f1, &mut f2);
+        (f1, f2)
+    }
+}
+
+/// Cruzamento de genomas autoadaptativos: os genes são cruzados pelo *cruzador* informado e os
+/// dois filhos recebem a média dos passos de mutação dos pais, a recombinação intermediária
+/// usada nas estratégias evolutivas.
+pub struct CruzadorAutoAdaptativo<C> {
+    pub cruzador: C,
+}
+
+impl<C> CruzadorAutoAdaptativo<C> {
+    pub fn criar(cruzador: C) -> Self {
+        CruzadorAutoAdaptativo { cruzador }
+    }
+}
+
+impl<C: Cruzador<Vec<f64>>> Cruzador<GenomaAutoAdaptativo> for CruzadorAutoAdaptativo<C> {
+    fn cruzar(&self,
+              aleatorio: &mut Aleatorio,
+              primeiro: &GenomaAutoAdaptativo,
+              segundo: &GenomaAutoAdaptativo)
+              -> (GenomaAutoAdaptativo, GenomaAutoAdaptativo) {
+        let (genes1, genes2) = self.cruzador.cruzar(aleatorio, &primeiro.genes, &segundo.genes);
+        let passos: Vec<f64> = primeiro.passos
+                                       .iter()
+                                       .zip(segundo.passos.iter())
+                                       .map(|(a, b)| (a + b) / 2.0)
+                                       .collect();
+        (GenomaAutoAdaptativo {
+            genes: genes1,
+            passos: passos.clone(),
+        },
+         GenomaAutoAdaptativo {
+            genes: genes2,
+            passos,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    struct Soma;
+
+    impl Aptidao<[f64; 3]> for Soma {
+        fn calcular_aptidao(&self, genes: &[f64; 3]) -> f64 {
+            genes.iter().sum()
+        }
+    }
+
+    fn dentro(limites: &Limites, genes: &[f64]) -> bool {
+        genes.iter().enumerate().all(|(i, v)| {
+            let (minimo, maximo) = limites.intervalo(i);
+            *v >= minimo && *v <= maximo
+        })
+    }
+
+    #[test]
+    fn deve_gerar_filhos_dentro_dos_limites() {
+        let mut aleatorio = Aleatorio::semente(20);
+        let limites = Limites::criar(vec![(-1.0, 1.0), (0.0, 10.0), (-5.0, 5.0)]);
+        let primeiro = [-1.0, 9.5, 0.0];
+        let segundo = [1.0, 10.0, 4.5];
+
+        let blx = CruzadorBlx::criar(0.5, limites.clone());
+        let sbx = CruzadorSbx::criar(2.0, limites.clone());
+        let heuristico = CruzadorHeuristico::criar(Soma, Operacao::Max, limites.clone());
+        for _ in 0..100 {
+            let (f1, f2) = blx.cruzar(&mut aleatorio, &primeiro, &segundo);
+            assert!(dentro(&limites, &f1) && dentro(&limites, &f2));
+            let (f1, f2) = sbx.cruzar(&mut aleatorio, &primeiro, &segundo);
+            assert!(dentro(&limites, &f1) && dentro(&limites, &f2));
+            let (f1, f2) = heuristico.cruzar(&mut aleatorio, &primeiro, &segundo);
+            assert!(dentro(&limites, &f1) && dentro(&limites, &f2));
+        }
+
+        // Com pais iguais o intervalo é vazio e os filhos repetem os pais
+        let (f1, _) = blx.cruzar(&mut aleatorio, &vec![0.0, 5.0], &vec![0.0, 5.0]);
+        assert_eq!(vec![0.0, 5.0], f1);
+    }
+
+    #[test]
+    fn deve_combinar_os_pais() {
+        let mut aleatorio = Aleatorio::semente(21);
+        let primeiro = vec![0.0, 10.0];
+        let segundo = vec![4.0, 20.0];
+
+        let (f1, f2) = CruzadorAritmetico::criar(0.25).cruzar(&mut aleatorio, &primeiro, &segundo);
+        assert_eq!(vec![3.0, 17.5], f1);
+        assert_eq!(vec![1.0, 12.5], f2);
+
+        // A média dos filhos do SBX é a média dos pais
+        let sbx = CruzadorSbx::criar(5.0, Limites::iguais(-100.0, 100.0));
+        let (f1, f2) = sbx.cruzar(&mut aleatorio, &primeiro, &segundo);
+        assert!((f1[0] + f2[0] - 4.0).abs() < 1e-9);
+        assert!((f1[1] + f2[1] - 30.0).abs() < 1e-9);
+
+        // O heurístico parte do melhor pai para longe do pior
+        let heuristico = CruzadorHeuristico::criar(Soma, Operacao::Max, Limites::iguais(0.0, 9.0));
+        let (f1, f2) = heuristico.cruzar(&mut aleatorio, &[1.0, 1.0, 1.0], &[2.0, 2.0, 2.0]);
+        assert!(f1.iter().chain(f2.iter()).all(|v| *v >= 2.0));
+    }
+}
@@ -0,0 +1,110 @@
+//! # Limites
+//!
+//! Intervalo de valores válidos de cada gene de um genoma real. Os operadores que criam valores
+//! novos usam os limites para não gerar genes fora do domínio do problema.
+
+use aleatorio::Aleatorio;
+
+/// O que fazer com um valor que saiu do intervalo do gene.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PoliticaLimites {
+    /// Usa o limite mais próximo
+    Limitar,
+    /// Rebate o valor no limite, como um espelho
+    Refletir,
+    /// O que passa de um limite volta pelo outro
+    Circular,
+    /// Sorteia um novo valor dentro do intervalo
+    Sortear,
+}
+
+/// Intervalo [mínimo, máximo] de cada gene. Quando existem menos intervalos que genes o último
+/// intervalo vale para todos os genes restantes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Limites {
+    pub intervalos: Vec<(f64, f64)>,
+}
+
+impl Limites {
+    /// Cria limites com um intervalo para cada gene.
+    pub fn criar(intervalos: Vec<(f64, f64)>) -> Self {
+        Limites { intervalos }
+    }
+
+    /// Cria limites com o mesmo intervalo para todos os genes.
+    pub fn iguais(minimo: f64, maximo: f64) -> Self {
+        Limites::criar(vec![(minimo, maximo)])
+    }
+
+    /// Intervalo do gene na posição *gene*. Sem intervalos o gene não tem limites.
+    pub fn intervalo(&self, gene: usize) -> (f64, f64) {
+        self.intervalos
+            .get(gene)
+            .or_else(|| self.intervalos.last())
+            .cloned()
+            .unwrap_or((f64::NEG_INFINITY, f64::INFINITY))
+    }
+
+    /// Traz o valor para dentro do intervalo do gene.
+    pub fn limitar(&self, gene: usize, valor: f64) -> f64 {
+        let (minimo, maximo) = self.intervalo(gene);
+        valor.max(minimo).min(maximo)
+    }
+
+    /// Traz o valor para dentro do intervalo do gene usando a *politica*. Quando o intervalo é
+    /// infinito o valor é apenas limitado.
+    pub fn corrigir(&self,
+                    gene: usize,
+                    valor: f64,
+                    politica: PoliticaLimites,
+                    aleatorio: &mut Aleatorio)
+                    -> f64 {
+        let (minimo, maximo) = self.intervalo(gene);
+        let largura = maximo - minimo;
+        if (valor >= minimo && valor <= maximo) || !(largura.is_finite() && largura > 0.0) {
+            return self.limitar(gene, valor);
+        }
+
+        match politica {
+            PoliticaLimites::Limitar => self.limitar(gene, valor),
+            PoliticaLimites::Refletir => {
+                let distancia = (valor - minimo).rem_euclid(2.0 * largura);
+                if distancia > largura {
+                    maximo - (distancia - largura)
+                } else {
+                    minimo + distancia
+                }
+            }
+            PoliticaLimites::Circular => minimo + (valor - minimo).rem_euclid(largura),
+            PoliticaLimites::Sortear => aleatorio.intervalo(minimo, maximo),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn deve_corrigir_valores_fora_dos_limites() {
+        let mut aleatorio = Aleatorio::semente(21);
+        let limites = Limites::criar(vec![(0.0, 10.0), (-1.0, 1.0)]);
+        let corrige = |valor, politica, aleatorio: &mut Aleatorio| {
+            limites.corrigir(0, valor, politica, aleatorio)
+        };
+
+        assert_eq!(10.0, corrige(12.0, PoliticaLimites::Limitar, &mut aleatorio));
+        assert_eq!(8.0, corrige(12.0, PoliticaLimites::Refletir, &mut aleatorio));
+        assert_eq!(3.0, corrige(-3.0, PoliticaLimites::Refletir, &mut aleatorio));
+        assert_eq!(2.0, corrige(12.0, PoliticaLimites::Circular, &mut aleatorio));
+        assert_eq!(7.0, corrige(-3.0, PoliticaLimites::Circular, &mut aleatorio));
+        assert_eq!(5.0, corrige(5.0, PoliticaLimites::Sortear, &mut aleatorio));
+        let sorteado = corrige(-3.0, PoliticaLimites::Sortear, &mut aleatorio);
+        assert!((0.0..10.0).contains(&sorteado));
+
+        // O último intervalo vale para os genes restantes
+        assert_eq!(-1.0, limites.corrigir(5, -4.0, PoliticaLimites::Limitar, &mut aleatorio));
+        assert_eq!(7.5, Limites::criar(Vec::new()).limitar(0, 7.5));
+    }
+}
@@ -7,22 +7,38 @@ pub mod cruzamento;
 pub mod mutacao;
 /// Métodos de seleção para uso no cruzamento
 pub mod selecao;
+/// Limites dos genes de genomas reais
+pub mod limites;
 
 use populacao::*;
+use aleatorio::Aleatorio;
+
+use std::marker::PhantomData;
 
 
 
 pub trait Evolucao<Gene> {
-    fn evoluir(&self, pop: &mut Populacao<Gene>) -> Populacao<Gene>;
+    /// Gera uma nova população a partir de *pop*. Toda a aleatoriedade da evolução deve vir de
+    /// *aleatorio*, para que a execução possa ser reproduzida.
+    fn evoluir(&self, pop: &mut Populacao<Gene>, aleatorio: &mut Aleatorio) -> Populacao<Gene>;
 }
 
-pub struct EvolucaoMista<Gene> {
-    evolucoes: Vec<Box<Evolucao<Gene>>>,
+pub struct EvolucaoMista<Gene, Evo: ?Sized = dyn Evolucao<Gene>> {
+    evolucoes: Vec<Box<Evo>>,
+
+    // Para satisfazer o compilador
+    phanton: PhantomData<fn(Gene)>,
 }
 
+/// Evolução mista que pode ser enviada para outras threads, como nas ilhas do `ModeloIlhas`.
+pub type EvolucaoMistaIlhas<Gene> = EvolucaoMista<Gene, dyn Evolucao<Gene> + Send + Sync>;
+
 impl<Gene> EvolucaoMista<Gene> {
     pub fn criar() -> Self {
-        EvolucaoMista { evolucoes: Vec::new() }
+        EvolucaoMista {
+            evolucoes: Vec::new(),
+            phanton: PhantomData,
+        }
     }
 
     pub fn adicionar<Evo>(&mut self, evolucao: Evo)
@@ -32,14 +48,35 @@ impl<Gene> EvolucaoMista<Gene> {
     }
 }
 
-impl<Gene> Evolucao<Gene> for EvolucaoMista<Gene> {
-    fn evoluir(&self, populacao: &mut Populacao<Gene>) -> Populacao<Gene> {
+impl<Gene> EvolucaoMistaIlhas<Gene> {
+    /// Cria uma evolução mista que só aceita evoluções que podem ser usadas em outras threads.
+    pub fn criar_para_ilhas() -> Self {
+        EvolucaoMista {
+            evolucoes: Vec::new(),
+            phanton: PhantomData,
+        }
+    }
+
+    pub fn adicionar<Evo>(&mut self, evolucao: Evo)
+        where Evo: Evolucao<Gene> + Send + Sync + 'static
+    {
+        self.evolucoes.push(Box::new(evolucao));
+    }
+}
+
+impl<Gene, Evo> Evolucao<Gene> for EvolucaoMista<Gene, Evo>
+    where Evo: Evolucao<Gene> + ?Sized
+{
+    fn evoluir(&self,
+               populacao: &mut Populacao<Gene>,
+               aleatorio: &mut Aleatorio)
+               -> Populacao<Gene> {
 
         let mut nova_populacao = populacao.preparar_nova_geracao();
 
         for ev in self.evolucoes.iter() {
 
-            let pop = ev.evoluir(populacao);
+            let pop = ev.evoluir(populacao, aleatorio);
             nova_populacao.reter(pop);
         }
 
@@ -51,7 +88,6 @@ impl<Gene> Evolucao<Gene> for EvolucaoMista<Gene> {
 mod test {
 
     use super::*;
-    use populacao::*;
     use aptidao::*;
 
     #[test]
@@ -67,7 +103,7 @@ mod test {
         pop.adicionar(1);
 
 
-        let new_pop = ev.evoluir(&mut pop);
+        let new_pop = ev.evoluir(&mut pop, &mut Aleatorio::semente(0));
         assert_eq!(3, new_pop.individuos.len());
 
         assert_eq!(2, new_pop.individuos[0].genes);
@@ -81,7 +117,7 @@ mod test {
     }
 
     impl Evolucao<usize> for EvolucaoTest {
-        fn evoluir(&self, pop: &mut Populacao<usize>) -> Populacao<usize> {
+        fn evoluir(&self, pop: &mut Populacao<usize>, _: &mut Aleatorio) -> Populacao<usize> {
 
 
             let mut nova_populacao = pop.preparar_nova_geracao();
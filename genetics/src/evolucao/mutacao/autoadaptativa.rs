@@ -0,0 +1,149 @@
+//! # Mutação autoadaptativa
+//!
+//! Como nas estratégias evolutivas, cada indivíduo carrega junto dos genes os passos de mutação
+//! usados para perturbá-los. A cada mutação os passos são alterados primeiro, com distribuição
+//! log-normal, e depois usados para perturbar os genes. Passos adequados geram filhos mais
+//! aptos, que sobrevivem e passam os seus passos adiante, assim a própria evolução ajusta o
+//! tamanho das mutações sem depender de uma regra externa.
+
+use aleatorio::Aleatorio;
+use evolucao::limites::{Limites, PoliticaLimites};
+use evolucao::mutacao::Mutagenico;
+use populacao::CriadorIndividuos;
+
+/// Genoma real com os seus passos de mutação. Pode ter um passo para cada gene ou um único
+/// passo compartilhado por todos.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenomaAutoAdaptativo {
+    pub genes: Vec<f64>,
+    pub passos: Vec<f64>,
+}
+
+impl GenomaAutoAdaptativo {
+    /// Cria o genoma com um passo *passo_inicial* para cada gene.
+    pub fn criar(genes: Vec<f64>, passo_inicial: f64) -> Self {
+        let passos = vec![passo_inicial; genes.len()];
+        GenomaAutoAdaptativo { genes, passos }
+    }
+
+    /// Passo de mutação do gene na posição *gene*.
+    pub fn passo(&self, gene: usize) -> f64 {
+        self.passos[gene.min(self.passos.len() - 1)]
+    }
+}
+
+/// Cria indivíduos com *quantidade* genes sorteados dentro dos limites, que devem ser finitos,
+/// todos com o passo *passo_inicial*.
+pub struct CriadorAutoAdaptativo {
+    pub quantidade: usize,
+    pub limites: Limites,
+    pub passo_inicial: f64,
+}
+
+impl CriadorIndividuos<GenomaAutoAdaptativo> for CriadorAutoAdaptativo {
+    fn criar(&self, aleatorio: &mut Aleatorio) -> GenomaAutoAdaptativo {
+        let genes = (0..self.quantidade)
+                        .map(|gene| {
+                            let (minimo, maximo) = self.limites.intervalo(gene);
+                            aleatorio.intervalo(minimo, maximo)
+                        })
+                        .collect();
+        GenomaAutoAdaptativo::criar(genes, self.passo_inicial)
+    }
+}
+
+/// Mutação autoadaptativa: os passos são multiplicados por exp(τ' N(0, 1) + τ Nᵢ(0, 1)), com
+/// as taxas de aprendizado τ' = 1 / √(2n) e τ = 1 / √(2√n), e cada gene recebe uma perturbação
+/// normal com o seu novo passo. Com um único passo a taxa é 1 / √n. Diferente dos outros
+/// mutagênicos, todos os genes são perturbados em cada mutação.
+pub struct MutacaoAutoAdaptativa {
+    pub limites: Limites,
+    pub politica: PoliticaLimites,
+    /// Menor passo permitido, evita que a busca pare por completo
+    pub passo_minimo: f64,
+}
+
+impl MutacaoAutoAdaptativa {
+    pub fn criar(limites: Limites) -> Self {
+        MutacaoAutoAdaptativa {
+            limites,
+            politica: PoliticaLimites::Limitar,
+            passo_minimo: 1e-8,
+        }
+    }
+}
+
+impl Mutagenico<GenomaAutoAdaptativo> for MutacaoAutoAdaptativa {
+    fn mutar(&self,
+             gene: &GenomaAutoAdaptativo,
+             aleatorio: &mut Aleatorio)
+             -> GenomaAutoAdaptativo {
+        let mut mutante = gene.clone();
+        let n = mutante.genes.len() as f64;
+        if mutante.genes.is_empty() || mutante.passos.is_empty() {
+            return mutante;
+        }
+
+        if mutante.passos.len() == 1 {
+            let passo = mutante.passos[0] * (aleatorio.normal() / n.sqrt()).exp();
+            mutante.passos[0] = passo.max(self.passo_minimo);
+        } else {
+            let global = aleatorio.normal() / (2.0 * n).sqrt();
+            let local = 1.0 / (2.0 * n.sqrt()).sqrt();
+            for passo in mutante.passos.iter_mut() {
+                *passo = (*passo * (global + local * aleatorio.normal()).exp())
+                             .max(self.passo_minimo);
+            }
+        }
+
+        for i in 0..mutante.genes.len() {
+            let valor = mutante.genes[i] + mutante.passo(i) * aleatorio.normal();
+            mutante.genes[i] = self.limites.corrigir(i, valor, self.politica, aleatorio);
+        }
+        mutante
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use evolucao::cruzamento::{Cruzador, CruzadorAutoAdaptativo, CruzadorPontos};
+
+    fn esfera(genoma: &GenomaAutoAdaptativo) -> f64 {
+        genoma.genes.iter().map(|gene| gene * gene).sum()
+    }
+
+    #[test]
+    fn deve_ajustar_os_passos_durante_a_busca() {
+        let mut aleatorio = Aleatorio::semente(61);
+        let limites = Limites::iguais(-10.0, 10.0);
+        let mutacao = MutacaoAutoAdaptativa::criar(limites.clone());
+        let criador = CriadorAutoAdaptativo {
+            quantidade: 5,
+            limites,
+            passo_inicial: 1.0,
+        };
+
+        // Estratégia (1 + 10): o melhor entre o pai e os filhos segue para a próxima geração
+        let mut pai = criador.criar(&mut aleatorio);
+        for _ in 0..300 {
+            for _ in 0..10 {
+                let filho = mutacao.mutar(&pai, &mut aleatorio);
+                assert!(filho.genes.iter().all(|gene| (-10.0..=10.0).contains(gene)));
+                if esfera(&filho) < esfera(&pai) {
+                    pai = filho;
+                }
+            }
+        }
+        assert!(esfera(&pai) < 1e-3, "{}", esfera(&pai));
+        assert!(pai.passos.iter().all(|passo| *passo < 0.1), "{:?}", pai.passos);
+
+        // No cruzamento os filhos recebem a média dos passos dos pais
+        let outro = GenomaAutoAdaptativo::criar(vec![1.0; 5], 1.0);
+        let cruzador = CruzadorAutoAdaptativo::criar(CruzadorPontos::criar(1));
+        let (f1, f2) = cruzador.cruzar(&mut aleatorio, &pai, &outro);
+        assert_eq!(f1.passos, f2.passos);
+        assert_eq!((pai.passo(0) + 1.0) / 2.0, f1.passo(0));
+    }
+}
@@ -0,0 +1,71 @@
+//! # Mutação de cadeias de bits
+
+use aleatorio::Aleatorio;
+use binario::CadeiaBits;
+use evolucao::mutacao::{Mutagenico, MutagenicoGene};
+
+/// Mutação bit a bit: cada bit da cadeia é invertido com a chance informada, normalmente
+/// 1 / tamanho da cadeia.
+pub struct MutacaoBits {
+    pub chance_bit: f64,
+}
+
+impl MutacaoBits {
+    pub fn criar(chance_bit: f64) -> Self {
+        MutacaoBits { chance_bit }
+    }
+}
+
+impl Mutagenico<CadeiaBits> for MutacaoBits {
+    fn mutar(&self, gene: &CadeiaBits, aleatorio: &mut Aleatorio) -> CadeiaBits {
+        let mut mutante = gene.clone();
+        if self.chance_bit <= 0.0 {
+            return mutante;
+        }
+
+        // Sorteia quantos bits pular até a próxima inversão em vez de sortear cada bit
+        let mut posicao = 0;
+        while posicao < mutante.len() {
+            if self.chance_bit < 1.0 {
+                let salto = (1.0 - aleatorio.chance()).ln() / (1.0 - self.chance_bit).ln();
+                posicao = posicao.saturating_add(salto as usize);
+                if posicao >= mutante.len() {
+                    break;
+                }
+            }
+            mutante.inverter(posicao);
+            posicao += 1;
+        }
+        mutante
+    }
+}
+
+/// Na mutação por gene cada bit sorteado é invertido.
+impl MutagenicoGene<CadeiaBits> for MutacaoBits {
+    fn quantidade_genes(&self, gene: &CadeiaBits) -> usize {
+        gene.len()
+    }
+
+    fn mutar_gene(&self, gene: &mut CadeiaBits, posicao: usize, _: usize, _: &mut Aleatorio) {
+        gene.inverter(posicao);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn deve_inverter_bits_com_a_chance_informada() {
+        let mut aleatorio = Aleatorio::semente(53);
+        let gene = CadeiaBits::criar(1000);
+
+        assert_eq!(gene, MutacaoBits::criar(0.0).mutar(&gene, &mut aleatorio));
+        assert_eq!(1000, MutacaoBits::criar(1.0).mutar(&gene, &mut aleatorio).uns());
+
+        let mutacao = MutacaoBits::criar(0.01);
+        let invertidos: usize = (0..100).map(|_| mutacao.mutar(&gene, &mut aleatorio).uns()).sum();
+        assert!(invertidos > 800 && invertidos < 1200, "{}", invertidos);
+    }
+}
@@ -0,0 +1,163 @@
+//! # Controle da chance de mutação
+//!
+//! Estratégias para variar a chance de mutação ao longo da execução em vez de usar o mesmo
+//! valor em todas as gerações. A `Mutacao` e a `MutacaoPorGene` consultam o controle com a
+//! geração da população a cada evolução e depois informam quantos mutantes ficaram mais aptos
+//! que o indivíduo original, permitindo tanto cronogramas fixos quanto regras adaptativas.
+
+use std::sync::Mutex;
+
+pub trait ControleMutacao {
+    /// Chance de mutação usada na *geracao*.
+    fn chance(&self, geracao: usize) -> f64;
+
+    /// Resultado das mutações de uma geração: dos *mutantes* gerados, *sucessos* ficaram mais
+    /// aptos que o indivíduo original. Por padrão o resultado é ignorado.
+    fn resultado(&self, _mutantes: usize, _sucessos: usize) {}
+}
+
+/// Permite escolher o controle em tempo de execução.
+impl<C: ControleMutacao + ?Sized> ControleMutacao for Box<C> {
+    fn chance(&self, geracao: usize) -> f64 {
+        (**self).chance(geracao)
+    }
+
+    fn resultado(&self, mutantes: usize, sucessos: usize) {
+        (**self).resultado(mutantes, sucessos)
+    }
+}
+
+/// Decaimento linear: a chance vai de *chance_inicial* até *chance_final* ao longo de
+/// *geracoes* gerações e se mantém em *chance_final* depois disso.
+pub struct DecaimentoLinear {
+    pub chance_inicial: f64,
+    pub chance_final: f64,
+    pub geracoes: usize,
+}
+
+impl DecaimentoLinear {
+    pub fn criar(chance_inicial: f64, chance_final: f64, geracoes: usize) -> Self {
+        DecaimentoLinear {
+            chance_inicial,
+            chance_final,
+            geracoes,
+        }
+    }
+}
+
+impl ControleMutacao for DecaimentoLinear {
+    fn chance(&self, geracao: usize) -> f64 {
+        let progresso = (geracao as f64 / self.geracoes.max(1) as f64).min(1.0);
+        self.chance_inicial * (1.0 - progresso) + self.chance_final * progresso
+    }
+}
+
+/// Decaimento exponencial: a chance é multiplicada por *fator* a cada geração.
+pub struct DecaimentoExponencial {
+    /// Chance na geração zero
+    pub chance_inicial: f64,
+    /// Fator, entre 0 e 1, que multiplica a chance a cada geração
+    pub fator: f64,
+    /// Menor chance usada, evita que a mutação desapareça
+    pub chance_minima: f64,
+}
+
+impl DecaimentoExponencial {
+    pub fn criar(chance_inicial: f64, fator: f64) -> Self {
+        DecaimentoExponencial {
+            chance_inicial,
+            fator,
+            chance_minima: 0.001,
+        }
+    }
+}
+
+impl ControleMutacao for DecaimentoExponencial {
+    fn chance(&self, geracao: usize) -> f64 {
+        let chance = self.chance_inicial * self.fator.powi(geracao as i32);
+        chance.max(self.chance_minima)
+    }
+}
+
+/// Regra de 1/5 de sucesso de Rechenberg: quando mais de um quinto dos mutantes supera o
+/// indivíduo original a busca está indo bem e a chance aumenta; quando menos de um quinto
+/// supera, a chance diminui. A chance muda a cada evolução, portanto a mesma instância não deve
+/// ser usada em mais de uma mutação.
+pub struct RegraUmQuinto {
+    chance: Mutex<f64>,
+    /// Fator, entre 0 e 1, que multiplica a chance quando há poucos sucessos e a divide quando
+    /// há muitos
+    pub fator: f64,
+    pub chance_minima: f64,
+    pub chance_maxima: f64,
+}
+
+impl RegraUmQuinto {
+    pub fn criar(chance_inicial: f64) -> Self {
+        RegraUmQuinto {
+            chance: Mutex::new(chance_inicial),
+            fator: 0.85,
+            chance_minima: 0.001,
+            chance_maxima: 1.0,
+        }
+    }
+}
+
+impl ControleMutacao for RegraUmQuinto {
+    fn chance(&self, _: usize) -> f64 {
+        *self.chance.lock().unwrap()
+    }
+
+    fn resultado(&self, mutantes: usize, sucessos: usize) {
+        if mutantes == 0 {
+            return;
+        }
+
+        let mut chance = self.chance.lock().unwrap();
+        let taxa_sucesso = sucessos as f64 / mutantes as f64;
+        if taxa_sucesso > 0.2 {
+            *chance /= self.fator;
+        } else if taxa_sucesso < 0.2 {
+            *chance *= self.fator;
+        }
+        *chance = chance.clamp(self.chance_minima, self.chance_maxima);
+        trace!("Taxa de sucesso {}, chance de mutação {}", taxa_sucesso, *chance);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn deve_decair_com_as_geracoes() {
+        let linear = DecaimentoLinear::criar(0.5, 0.1, 100);
+        assert_eq!(0.5, linear.chance(0));
+        assert!((linear.chance(50) - 0.3).abs() < 1e-9);
+        assert_eq!(0.1, linear.chance(100));
+        assert_eq!(0.1, linear.chance(500));
+
+        let exponencial = DecaimentoExponencial::criar(0.5, 0.5);
+        assert_eq!(0.5, exponencial.chance(0));
+        assert_eq!(0.125, exponencial.chance(2));
+        assert_eq!(0.001, exponencial.chance(100));
+    }
+
+    #[test]
+    fn regra_um_quinto_deve_seguir_a_taxa_de_sucesso() {
+        let regra = RegraUmQuinto::criar(0.1);
+        regra.resultado(10, 5);
+        assert!(regra.chance(0) > 0.1);
+
+        let regra = RegraUmQuinto::criar(0.1);
+        regra.resultado(10, 2);
+        assert_eq!(0.1, regra.chance(0));
+        regra.resultado(0, 0);
+        assert_eq!(0.1, regra.chance(0));
+        for _ in 0..100 {
+            regra.resultado(10, 0);
+        }
+        assert_eq!(0.001, regra.chance(0));
+    }
+}
@@ -0,0 +1,341 @@
+
+use populacao::Populacao;
+use evolucao::*;
+use aleatorio::Aleatorio;
+
+use std::marker::PhantomData;
+use std::cmp::Ordering;
+
+
+/// Mutações para genomas de números reais
+pub mod real;
+/// Mutações que preservam permutações
+pub mod permutacao;
+/// Mutação de cadeias de bits
+pub mod binario;
+/// Variação da chance de mutação ao longo da execução
+pub mod controle;
+/// Genoma real com passos de mutação autoadaptativos
+pub mod autoadaptativa;
+
+pub use self::real::*;
+pub use self::permutacao::*;
+pub use self::binario::*;
+pub use self::controle::*;
+pub use self::autoadaptativa::*;
+
+pub trait Mutagenico<Gene> {
+    fn mutar(&self, gene: &Gene, aleatorio: &mut Aleatorio) -> Gene;
+
+    /// Muta o gene sabendo a geração em que a população está, para mutações que mudam ao longo
+    /// da execução. Por padrão ignora a geração.
+    fn mutar_na_geracao(&self, gene: &Gene, _geracao: usize, aleatorio: &mut Aleatorio) -> Gene {
+        self.mutar(gene, aleatorio)
+    }
+}
+
+/// Mutação de uma única posição do genoma, usada pela `MutacaoPorGene` para decidir
+/// separadamente se cada posição deve ser mutada.
+pub trait MutagenicoGene<Gene> {
+    /// Quantidade de posições de *gene* que podem ser mutadas.
+    fn quantidade_genes(&self, gene: &Gene) -> usize;
+
+    /// Muta a posição *posicao* de *gene*. A *geracao* é a da população, para mutações que
+    /// mudam ao longo da execução.
+    fn mutar_gene(&self,
+                  gene: &mut Gene,
+                  posicao: usize,
+                  geracao: usize,
+                  aleatorio: &mut Aleatorio);
+}
+
+pub struct MutagenicoVetor {
+    pub estados: Vec<usize>,
+}
+
+impl Mutagenico<Vec<usize>> for MutagenicoVetor {
+    fn mutar(&self, gene: &Vec<usize>, aleatorio: &mut Aleatorio) -> Vec<usize> {
+
+        let indice = aleatorio.intervalo(0, self.estados.len());
+        let estado = aleatorio.intervalo(0, self.estados[indice]);
+
+        let mut mutante = gene.clone();
+        mutante[indice] = estado;
+
+        mutante
+    }
+}
+
+impl MutagenicoGene<Vec<usize>> for MutagenicoVetor {
+    fn quantidade_genes(&self, gene: &Vec<usize>) -> usize {
+        gene.len().min(self.estados.len())
+    }
+
+    fn mutar_gene(&self,
+                  gene: &mut Vec<usize>,
+                  posicao: usize,
+                  _: usize,
+                  aleatorio: &mut Aleatorio) {
+        gene[posicao] = aleatorio.intervalo(0, self.estados[posicao]);
+    }
+}
+
+
+
+pub struct Mutacao<Mut, Gene>
+    where Mut: Mutagenico<Gene>
+{
+    mutagenico: Mut,
+    chance: f64,
+    controle: Option<Box<dyn ControleMutacao + Send + Sync>>,
+
+    // Para satisfazer o compilador
+    phanton: PhantomData<Gene>,
+}
+
+impl<Mut, Gene> Mutacao<Mut, Gene>
+    where Mut: Mutagenico<Gene>
+{
+    #[allow(clippy::redundant_field_names)]
+    pub fn criar(chance: f64, mutagenico: Mut) -> Self {
+        Mutacao {
+            chance: chance,
+            mutagenico: mutagenico,
+            controle: None,
+            phanton: PhantomData,
+        }
+    }
+
+    /// Passa a usar a chance definida pelo *controle* a cada geração no lugar da chance fixa.
+    pub fn controle<C>(&mut self, controle: C)
+        where C: ControleMutacao + Send + Sync + 'static
+    {
+        self.controle = Some(Box::new(controle));
+    }
+}
+
+
+/// Implementação para quando a mutação é um operador usado na evolução do AG.
+impl<Mut, Gene> Evolucao<Gene> for Mutacao<Mut, Gene>
+    where Mut: Mutagenico<Gene>,
+          Gene: Sync
+{
+    fn evoluir(&self, pop: &mut Populacao<Gene>, aleatorio: &mut Aleatorio) -> Populacao<Gene> {
+
+        let mut mutantes = pop.preparar_nova_geracao();
+
+        let geracao = pop.geracao();
+        let chance = chance_na_geracao(&self.controle, self.chance, geracao);
+        let mut genes = Vec::new();
+        let mut originais = Vec::new();
+        for ind in pop.individuos.iter() {
+            if chance > aleatorio.chance() {
+
+                genes.push(self.mutagenico.mutar_na_geracao(&ind.genes, geracao, aleatorio));
+                originais.push(ind.aptidao);
+            }
+        }
+        mutantes.adicionar_lote(genes);
+        informar_resultado(&self.controle, &originais, &mutantes);
+        mutantes
+    }
+}
+
+/// Mutação com chance por gene: cada posição de cada indivíduo é mutada com a chance
+/// informada, como na definição usual da taxa de mutação. Só os indivíduos com alguma posição
+/// mutada geram mutantes.
+pub struct MutacaoPorGene<Mut, Gene>
+    where Mut: MutagenicoGene<Gene>
+{
+    mutagenico: Mut,
+    chance_gene: f64,
+    pelo_menos_um: bool,
+    controle: Option<Box<dyn ControleMutacao + Send + Sync>>,
+
+    // Para satisfazer o compilador
+    phanton: PhantomData<Gene>,
+}
+
+impl<Mut, Gene> MutacaoPorGene<Mut, Gene>
+    where Mut: MutagenicoGene<Gene>
+{
+    pub fn criar(chance_gene: f64, mutagenico: Mut) -> Self {
+        MutacaoPorGene {
+            mutagenico,
+            chance_gene,
+            pelo_menos_um: false,
+            controle: None,
+            phanton: PhantomData,
+        }
+    }
+
+    /// Garante que todo indivíduo tenha pelo menos uma posição mutada: quando nenhuma posição é
+    /// sorteada, uma posição qualquer é mutada.
+    pub fn pelo_menos_um(&mut self, pelo_menos_um: bool) {
+        self.pelo_menos_um = pelo_menos_um;
+    }
+
+    /// Passa a usar a chance por gene definida pelo *controle* a cada geração no lugar da
+    /// chance fixa.
+    pub fn controle<C>(&mut self, controle: C)
+        where C: ControleMutacao + Send + Sync + 'static
+    {
+        self.controle = Some(Box::new(controle));
+    }
+
+    fn mutar(&self,
+             gene: &Gene,
+             chance_gene: f64,
+             geracao: usize,
+             aleatorio: &mut Aleatorio)
+             -> Option<Gene>
+        where Gene: Clone
+    {
+        let quantidade = self.mutagenico.quantidade_genes(gene);
+        let mut mutante = None;
+        for posicao in 0..quantidade {
+            if chance_gene > aleatorio.chance() {
+                let genes = mutante.get_or_insert_with(|| gene.clone());
+                self.mutagenico.mutar_gene(genes, posicao, geracao, aleatorio);
+            }
+        }
+
+        if mutante.is_none() && self.pelo_menos_um && quantidade > 0 {
+            let mut genes = gene.clone();
+            let posicao = aleatorio.intervalo(0, quantidade);
+            self.mutagenico.mutar_gene(&mut genes, posicao, geracao, aleatorio);
+            mutante = Some(genes);
+        }
+        mutante
+    }
+}
+
+impl<Mut, Gene> Evolucao<Gene> for MutacaoPorGene<Mut, Gene>
+    where Mut: MutagenicoGene<Gene>,
+          Gene: Clone + Sync
+{
+    fn evoluir(&self, pop: &mut Populacao<Gene>, aleatorio: &mut Aleatorio) -> Populacao<Gene> {
+
+        let mut mutantes = pop.preparar_nova_geracao();
+
+        let geracao = pop.geracao();
+        let chance_gene = chance_na_geracao(&self.controle, self.chance_gene, geracao);
+        let mut genes = Vec::new();
+        let mut originais = Vec::new();
+        for ind in pop.individuos.iter() {
+            if let Some(mutante) = self.mutar(&ind.genes, chance_gene, geracao, aleatorio) {
+                genes.push(mutante);
+                originais.push(ind.aptidao);
+            }
+        }
+        mutantes.adicionar_lote(genes);
+        informar_resultado(&self.controle, &originais, &mutantes);
+        mutantes
+    }
+}
+
+fn chance_na_geracao(controle: &Option<Box<dyn ControleMutacao + Send + Sync>>,
+                     chance: f64,
+                     geracao: usize)
+                     -> f64 {
+    match *controle {
+        Some(ref controle) => controle.chance(geracao),
+        None => chance,
+    }
+}
+
+/// Informa ao controle quantos *mutantes* ficaram mais aptos que os indivíduos originais, cujas
+/// aptidões estão em *originais* na mesma ordem.
+fn informar_resultado<Gene>(controle: &Option<Box<dyn ControleMutacao + Send + Sync>>,
+                            originais: &[f64],
+                            mutantes: &Populacao<Gene>) {
+    if let Some(ref controle) = *controle {
+        let operacao = mutantes.operacao();
+        let sucessos = mutantes.individuos
+                               .iter()
+                               .zip(originais.iter())
+                               .filter(|&(mutante, original)| {
+                                   operacao.compara(&mutante.aptidao, original) ==
+                                   Ordering::Greater
+                               })
+                               .count();
+        controle.resultado(originais.len(), sucessos);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use aptidao::AptidaoSimples;
+
+    /// Cada posição mutada soma 1 ao gene.
+    struct Contador;
+
+    impl MutagenicoGene<usize> for Contador {
+        fn quantidade_genes(&self, _: &usize) -> usize {
+            10
+        }
+
+        fn mutar_gene(&self, gene: &mut usize, _: usize, _: usize, _: &mut Aleatorio) {
+            *gene += 1;
+        }
+    }
+
+    impl Mutagenico<usize> for Contador {
+        fn mutar(&self, gene: &usize, _: &mut Aleatorio) -> usize {
+            gene + 1
+        }
+    }
+
+    #[test]
+    fn deve_mutar_cada_gene_com_a_chance_informada() {
+        let mut aleatorio = Aleatorio::semente(59);
+        let mut pop = Populacao::criar_vazia(AptidaoSimples, Operacao::Max);
+        for _ in 0..1000 {
+            pop.adicionar(0);
+        }
+
+        // Com chance 0.1 em 10 posições cada indivíduo tem em média uma mutação
+        let mutantes = MutacaoPorGene::criar(0.1, Contador).evoluir(&mut pop, &mut aleatorio);
+        let mutacoes: usize = mutantes.individuos.iter().map(|ind| ind.genes).sum();
+        assert!(mutacoes > 900 && mutacoes < 1100, "{}", mutacoes);
+        assert!(mutantes.individuos.len() < 700);
+
+        let mut mutacao = MutacaoPorGene::criar(0.0, Contador);
+        assert!(mutacao.evoluir(&mut pop, &mut aleatorio).individuos.is_empty());
+        mutacao.pelo_menos_um(true);
+        let mutantes = mutacao.evoluir(&mut pop, &mut aleatorio);
+        assert_eq!(1000, mutantes.individuos.len());
+        assert!(mutantes.individuos.iter().all(|ind| ind.genes == 1));
+    }
+
+    #[test]
+    fn deve_usar_a_chance_do_controle() {
+        let mut aleatorio = Aleatorio::semente(67);
+        let mut pop = Populacao::criar_vazia(AptidaoSimples, Operacao::Max);
+        for _ in 0..100 {
+            pop.adicionar(0);
+        }
+
+        let mut mutacao = Mutacao::criar(1.0, Contador);
+        mutacao.controle(DecaimentoLinear::criar(1.0, 0.0, 10));
+        assert_eq!(100, mutacao.evoluir(&mut pop, &mut aleatorio).individuos.len());
+        pop.definir_geracao(10);
+        assert!(mutacao.evoluir(&mut pop, &mut aleatorio).individuos.is_empty());
+
+        // Todo mutante do Contador é mais apto, então a regra de 1/5 aumenta a chance
+        let mut mutacao = MutacaoPorGene::criar(0.0, Contador);
+        mutacao.controle(RegraUmQuinto::criar(0.01));
+        let mut anterior = 0;
+        for _ in 0..40 {
+            let mutacoes: usize = mutacao.evoluir(&mut pop, &mut aleatorio)
+                                         .individuos
+                                         .iter()
+                                         .map(|ind| ind.genes)
+                                         .sum();
+            anterior = anterior.max(mutacoes);
+        }
+        assert_eq!(1000, anterior);
+    }
+}
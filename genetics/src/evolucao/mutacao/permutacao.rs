@@ -0,0 +1,175 @@
+//! # Mutações de permutações
+//!
+//! Mutagênicos para genomas que são permutações, como rotas e ordens de execução. Trocar um
+//! elemento por um valor qualquer gera repetições nesses genomas, os mutagênicos daqui apenas
+//! mudam os elementos de posição. Cada mutagênico aplica a sua operação *aplicacoes* vezes em
+//! cada mutação.
+
+use aleatorio::Aleatorio;
+use evolucao::mutacao::Mutagenico;
+
+/// Mutação por troca: dois elementos sorteados trocam de posição.
+pub struct MutacaoTroca {
+    pub aplicacoes: usize,
+}
+
+impl MutacaoTroca {
+    pub fn criar(aplicacoes: usize) -> Self {
+        MutacaoTroca { aplicacoes }
+    }
+}
+
+impl<T: Clone> Mutagenico<Vec<T>> for MutacaoTroca {
+    fn mutar(&self, gene: &Vec<T>, aleatorio: &mut Aleatorio) -> Vec<T> {
+        aplica(gene, self.aplicacoes, aleatorio, |genes, aleatorio| {
+            let (i, j) = dois_indices(genes.len(), aleatorio);
+            genes.swap(i, j);
+        })
+    }
+}
+
+/// Mutação por inserção: um elemento sorteado é retirado e inserido em outra posição,
+/// deslocando os elementos entre as duas posições.
+pub struct MutacaoInsercao {
+    pub aplicacoes: usize,
+}
+
+impl MutacaoInsercao {
+    pub fn criar(aplicacoes: usize) -> Self {
+        MutacaoInsercao { aplicacoes }
+    }
+}
+
+impl<T: Clone> Mutagenico<Vec<T>> for MutacaoInsercao {
+    fn mutar(&self, gene: &Vec<T>, aleatorio: &mut Aleatorio) -> Vec<T> {
+        aplica(gene, self.aplicacoes, aleatorio, |genes, aleatorio| {
+            let origem = aleatorio.intervalo(0, genes.len());
+            let mut destino = aleatorio.intervalo(0, genes.len() - 1);
+            if destino >= origem {
+                destino += 1;
+            }
+            let elemento = genes.remove(origem);
+            genes.insert(destino, elemento);
+        })
+    }
+}
+
+/// Mutação por inversão: o trecho entre duas posições sorteadas é invertido. Em rotas equivale
+/// ao movimento 2-opt, trocando apenas duas arestas.
+pub struct MutacaoInversao {
+    pub aplicacoes: usize,
+}
+
+impl MutacaoInversao {
+    pub fn criar(aplicacoes: usize) -> Self {
+        MutacaoInversao { aplicacoes }
+    }
+}
+
+impl<T: Clone> Mutagenico<Vec<T>> for MutacaoInversao {
+    fn mutar(&self, gene: &Vec<T>, aleatorio: &mut Aleatorio) -> Vec<T> {
+        aplica(gene, self.aplicacoes, aleatorio, |genes, aleatorio| {
+            let (inicio, fim) = dois_indices(genes.len(), aleatorio);
+            genes[inicio..fim + 1].reverse();
+        })
+    }
+}
+
+/// Mutação por embaralhamento: o trecho entre duas posições sorteadas é embaralhado.
+pub struct MutacaoEmbaralhamento {
+    pub aplicacoes: usize,
+}
+
+impl MutacaoEmbaralhamento {
+    pub fn criar(aplicacoes: usize) -> Self {
+        MutacaoEmbaralhamento { aplicacoes }
+    }
+}
+
+impl<T: Clone> Mutagenico<Vec<T>> for MutacaoEmbaralhamento {
+    fn mutar(&self, gene: &Vec<T>, aleatorio: &mut Aleatorio) -> Vec<T> {
+        aplica(gene, self.aplicacoes, aleatorio, |genes, aleatorio| {
+            let (inicio, fim) = dois_indices(genes.len(), aleatorio);
+            aleatorio.embaralhar(&mut genes[inicio..fim + 1]);
+        })
+    }
+}
+
+/// Aplica a *operacao* *aplicacoes* vezes numa cópia do gene. Genomas com menos de dois
+/// elementos não têm o que mudar de posição.
+fn aplica<T, F>(gene: &[T],
+                aplicacoes: usize,
+                aleatorio: &mut Aleatorio,
+                mut operacao: F)
+                -> Vec<T>
+    where T: Clone,
+          F: FnMut(&mut Vec<T>, &mut Aleatorio)
+{
+    let mut mutante = gene.to_vec();
+    if mutante.len() >= 2 {
+        for _ in 0..aplicacoes {
+            operacao(&mut mutante, aleatorio);
+        }
+    }
+    mutante
+}
+
+/// Sorteia duas posições diferentes, a menor primeiro.
+fn dois_indices(qtd: usize, aleatorio: &mut Aleatorio) -> (usize, usize) {
+    let i = aleatorio.intervalo(0, qtd);
+    let mut j = aleatorio.intervalo(0, qtd - 1);
+    if j >= i {
+        j += 1;
+    }
+    (i.min(j), i.max(j))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn valida_mutagenico<M: Mutagenico<Vec<usize>>>(mutagenico: M) {
+        let mut aleatorio = Aleatorio::semente(41);
+        for qtd in [0, 1, 2, 5, 30].iter() {
+            let mut gene: Vec<usize> = (0..*qtd).collect();
+            for _ in 0..50 {
+                gene = mutagenico.mutar(&gene, &mut aleatorio);
+                let mut ordenados = gene.clone();
+                ordenados.sort();
+                assert_eq!((0..*qtd).collect::<Vec<usize>>(), ordenados);
+            }
+        }
+    }
+
+    #[test]
+    fn deve_gerar_permutacoes_validas() {
+        valida_mutagenico(MutacaoTroca::criar(1));
+        valida_mutagenico(MutacaoInsercao::criar(2));
+        valida_mutagenico(MutacaoInversao::criar(1));
+        valida_mutagenico(MutacaoEmbaralhamento::criar(3));
+    }
+
+    #[test]
+    fn deve_mudar_apenas_as_posicoes_sorteadas() {
+        let mut aleatorio = Aleatorio::semente(43);
+        let gene: Vec<usize> = (0..20).collect();
+
+        let mutante = MutacaoTroca::criar(1).mutar(&gene, &mut aleatorio);
+        assert_eq!(2, gene.iter().zip(mutante.iter()).filter(|&(a, b)| a != b).count());
+
+        // Na inversão as posições alteradas formam um trecho em ordem decrescente
+        let mutante = MutacaoInversao::criar(1).mutar(&gene, &mut aleatorio);
+        let alterados: Vec<usize> = mutante.iter()
+                                           .enumerate()
+                                           .filter(|&(i, v)| i != *v)
+                                           .map(|(_, v)| *v)
+                                           .collect();
+        assert!(alterados.windows(2).all(|par| par[0] > par[1]));
+
+        // Na inserção a ordem relativa dos outros elementos é mantida
+        let mutante = MutacaoInsercao::criar(1).mutar(&gene, &mut aleatorio);
+        let fora_de_ordem = mutante.windows(2).filter(|par| par[0] > par[1]).count();
+        assert!(fora_de_ordem <= 1);
+    }
+}
@@ -0,0 +1,250 @@
+//! # Mutações reais
+//!
+//! Mutagênicos para genomas de números reais. Em vez de trocar o gene por um valor qualquer do
+//! intervalo, o que equivale a uma busca aleatória, estes perturbam o valor atual, gerando
+//! mutantes próximos do original com mais frequência. Assim como os outros mutagênicos, cada
+//! mutação altera um gene sorteado, ou cada gene com a sua chance quando usados na mutação por
+//! gene. Valores que saem dos limites são corrigidos pela política de limites do mutagênico.
+
+use aleatorio::Aleatorio;
+use evolucao::limites::{Limites, PoliticaLimites};
+use evolucao::mutacao::{Mutagenico, MutagenicoGene};
+
+/// Mutação gaussiana: soma ao gene um valor da distribuição normal com média 0 e desvio padrão
+/// *sigma*.
+pub struct MutacaoGaussiana {
+    pub sigma: f64,
+    pub limites: Limites,
+    pub politica: PoliticaLimites,
+}
+
+impl MutacaoGaussiana {
+    pub fn criar(sigma: f64, limites: Limites) -> Self {
+        MutacaoGaussiana {
+            sigma,
+            limites,
+            politica: PoliticaLimites::Limitar,
+        }
+    }
+
+    fn mutar_valor(&self,
+                   gene: usize,
+                   valor: f64,
+                   _: usize,
+                   aleatorio: &mut Aleatorio)
+                   -> f64 {
+        let mutado = valor + self.sigma * aleatorio.normal();
+        self.limites.corrigir(gene, mutado, self.politica, aleatorio)
+    }
+}
+
+/// Mutação polinomial de Deb: a perturbação segue uma distribuição polinomial proporcional ao
+/// tamanho do intervalo do gene. Precisa de limites finitos.
+pub struct MutacaoPolinomial {
+    /// Índice de distribuição η. Valores altos geram mutantes mais próximos do original,
+    /// normalmente entre 20 e 100
+    pub indice_distribuicao: f64,
+    pub limites: Limites,
+    pub politica: PoliticaLimites,
+}
+
+impl MutacaoPolinomial {
+    pub fn criar(indice_distribuicao: f64, limites: Limites) -> Self {
+        MutacaoPolinomial {
+            indice_distribuicao,
+            limites,
+            politica: PoliticaLimites::Limitar,
+        }
+    }
+
+    fn mutar_valor(&self,
+                   gene: usize,
+                   valor: f64,
+                   _: usize,
+                   aleatorio: &mut Aleatorio)
+                   -> f64 {
+        let (minimo, maximo) = self.limites.intervalo(gene);
+        let expoente = 1.0 / (self.indice_distribuicao + 1.0);
+        let u = aleatorio.chance();
+        let delta = if u < 0.5 {
+            (2.0 * u).powf(expoente) - 1.0
+        } else {
+            1.0 - (2.0 * (1.0 - u)).powf(expoente)
+        };
+
+        let mutado = valor + delta * (maximo - minimo);
+        self.limites.corrigir(gene, mutado, self.politica, aleatorio)
+    }
+}
+
+/// Mutação não uniforme de Michalewicz: no início da execução o gene pode ir para qualquer
+/// ponto do intervalo e, conforme as gerações passam, as perturbações ficam menores, refinando
+/// as soluções. Usa a geração da população, portanto só muda ao longo da execução quando usada
+/// pela `Mutacao`.
+pub struct MutacaoNaoUniforme {
+    /// Geração em que as perturbações chegam a zero, normalmente o total de gerações
+    pub geracoes: usize,
+    /// Quanto maior, mais rápido as perturbações diminuem
+    pub dependencia: f64,
+    pub limites: Limites,
+    pub politica: PoliticaLimites,
+}
+
+impl MutacaoNaoUniforme {
+    pub fn criar(geracoes: usize, limites: Limites) -> Self {
+        MutacaoNaoUniforme {
+            geracoes,
+            dependencia: 5.0,
+            limites,
+            politica: PoliticaLimites::Limitar,
+        }
+    }
+
+    /// Perturbação de no máximo *distancia* na *geracao*.
+    fn perturbacao(&self, geracao: usize, distancia: f64, aleatorio: &mut Aleatorio) -> f64 {
+        let progresso = (geracao as f64 / self.geracoes.max(1) as f64).min(1.0);
+        let r = aleatorio.chance();
+        distancia * (1.0 - r.powf((1.0 - progresso).powf(self.dependencia)))
+    }
+
+    fn mutar_valor(&self,
+                   gene: usize,
+                   valor: f64,
+                   geracao: usize,
+                   aleatorio: &mut Aleatorio)
+                   -> f64 {
+        let (minimo, maximo) = self.limites.intervalo(gene);
+        let mutado = if aleatorio.chance() < 0.5 {
+            valor + self.perturbacao(geracao, maximo - valor, aleatorio)
+        } else {
+            valor - self.perturbacao(geracao, valor - minimo, aleatorio)
+        };
+        self.limites.corrigir(gene, mutado, self.politica, aleatorio)
+    }
+}
+
+macro_rules! mutagenico_real {
+    ($mutagenico:ty) => {
+        impl Mutagenico<Vec<f64>> for $mutagenico {
+            fn mutar(&self, gene: &Vec<f64>, aleatorio: &mut Aleatorio) -> Vec<f64> {
+                self.mutar_na_geracao(gene, 0, aleatorio)
+            }
+
+            fn mutar_na_geracao(&self,
+                                gene: &Vec<f64>,
+                                geracao: usize,
+                                aleatorio: &mut Aleatorio)
+                                -> Vec<f64> {
+                let mut mutante = gene.clone();
+                if !mutante.is_empty() {
+                    let indice = aleatorio.intervalo(0, mutante.len());
+                    mutante[indice] = self.mutar_valor(indice, gene[indice], geracao, aleatorio);
+                }
+                mutante
+            }
+        }
+
+        impl<const N: usize> Mutagenico<[f64; N]> for $mutagenico {
+            fn mutar(&self, gene: &[f64; N], aleatorio: &mut Aleatorio) -> [f64; N] {
+                self.mutar_na_geracao(gene, 0, aleatorio)
+            }
+
+            fn mutar_na_geracao(&self,
+                                gene: &[f64; N],
+                                geracao: usize,
+                                aleatorio: &mut Aleatorio)
+                                -> [f64; N] {
+                let mut mutante = *gene;
+                if N > 0 {
+                    let indice = aleatorio.intervalo(0, N);
+                    mutante[indice] = self.mutar_valor(indice, gene[indice], geracao, aleatorio);
+                }
+                mutante
+            }
+        }
+
+        impl MutagenicoGene<Vec<f64>> for $mutagenico {
+            fn quantidade_genes(&self, gene: &Vec<f64>) -> usize {
+                gene.len()
+            }
+
+            fn mutar_gene(&self,
+                          gene: &mut Vec<f64>,
+                          posicao: usize,
+                          geracao: usize,
+                          aleatorio: &mut Aleatorio) {
+                gene[posicao] = self.mutar_valor(posicao, gene[posicao], geracao, aleatorio);
+            }
+        }
+
+        impl<const N: usize> MutagenicoGene<[f64; N]> for $mutagenico {
+            fn quantidade_genes(&self, _: &[f64; N]) -> usize {
+                N
+            }
+
+            fn mutar_gene(&self,
+                          gene: &mut [f64; N],
+                          posicao: usize,
+                          geracao: usize,
+                          aleatorio: &mut Aleatorio) {
+                gene[posicao] = self.mutar_valor(posicao, gene[posicao], geracao, aleatorio);
+            }
+        }
+    };
+}
+
+mutagenico_real!(MutacaoGaussiana);
+mutagenico_real!(MutacaoPolinomial);
+mutagenico_real!(MutacaoNaoUniforme);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn distancia(a: &[f64], b: &[f64]) -> f64 {
+        a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum()
+    }
+
+    #[test]
+    fn deve_mutar_um_gene_dentro_dos_limites() {
+        let mut aleatorio = Aleatorio::semente(31);
+        let limites = Limites::criar(vec![(-1.0, 1.0), (0.0, 100.0)]);
+        let gene = [0.9, 50.0];
+
+        let mut gaussiana = MutacaoGaussiana::criar(2.0, limites.clone());
+        gaussiana.politica = PoliticaLimites::Refletir;
+        let polinomial = MutacaoPolinomial::criar(20.0, limites.clone());
+        let nao_uniforme = MutacaoNaoUniforme::criar(100, limites.clone());
+        for _ in 0..200 {
+            for mutante in [gaussiana.mutar(&gene, &mut aleatorio),
+                            polinomial.mutar(&gene, &mut aleatorio),
+                            nao_uniforme.mutar_na_geracao(&gene, 30, &mut aleatorio)]
+                               .iter() {
+                assert!((-1.0..=1.0).contains(&mutante[0]));
+                assert!((0.0..=100.0).contains(&mutante[1]));
+                assert!(mutante[0] == gene[0] || mutante[1] == gene[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn mutacao_nao_uniforme_deve_diminuir_com_as_geracoes() {
+        let mut aleatorio = Aleatorio::semente(37);
+        let mutacao = MutacaoNaoUniforme::criar(100, Limites::iguais(-10.0, 10.0));
+        let gene = vec![0.0; 5];
+
+        let media = |geracao: usize, aleatorio: &mut Aleatorio| {
+            (0..500)
+                .map(|_| distancia(&gene, &mutacao.mutar_na_geracao(&gene, geracao, aleatorio)))
+                .sum::<f64>() / 500.0
+        };
+
+        let inicio = media(0, &mut aleatorio);
+        let meio = media(50, &mut aleatorio);
+        let fim = media(100, &mut aleatorio);
+        assert!(inicio > meio);
+        assert!(meio > fim);
+        assert_eq!(0.0, fim);
+    }
+}
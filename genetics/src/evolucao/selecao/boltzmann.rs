@@ -0,0 +1,77 @@
+use populacao::*;
+use evolucao::selecao::Seletor;
+use evolucao::selecao::roleta::{gera_roleta, recupera_posicao};
+use evolucao::selecao::escalonamento::EscalonamentoBoltzmann;
+use aleatorio::Aleatorio;
+
+/// Seleção de Boltzmann: roleta com pesos exp(aptidão / temperatura), onde a temperatura cai a
+/// cada geração. No começo a seleção é quase uniforme, preservando a diversidade, e ao longo da
+/// execução se concentra nos melhores indivíduos.
+pub struct SelecaoBoltzmann {
+    /// Temperatura na geração zero
+    pub temperatura_inicial: f64,
+    /// Fator, entre 0 e 1, que multiplica a temperatura a cada geração
+    pub resfriamento: f64,
+    /// Menor temperatura usada, evita que a seleção vire a escolha do melhor indivíduo
+    pub temperatura_minima: f64,
+}
+
+impl SelecaoBoltzmann {
+    pub fn criar(temperatura_inicial: f64, resfriamento: f64) -> Self {
+        SelecaoBoltzmann {
+            temperatura_inicial,
+            resfriamento,
+            temperatura_minima: 0.001,
+        }
+    }
+
+    /// Temperatura usada na seleção dos pais da população da *geracao* informada.
+    pub fn temperatura(&self, geracao: usize) -> f64 {
+        let temperatura = self.temperatura_inicial * self.resfriamento.powi(geracao as i32);
+        temperatura.max(self.temperatura_minima)
+    }
+}
+
+impl<Gene> Seletor<Gene> for SelecaoBoltzmann {
+    fn seleciona_pais(&self,
+                      pop: &Populacao<Gene>,
+                      quantidade: usize,
+                      aleatorio: &mut Aleatorio)
+                      -> Vec<usize> {
+
+        if pop.individuos.is_empty() {
+            return Vec::new();
+        }
+
+        let temperatura = self.temperatura(pop.geracao());
+        trace!("Temperatura {} na geração {}", temperatura, pop.geracao());
+        let roleta = gera_roleta(pop, &EscalonamentoBoltzmann { temperatura });
+        (0..quantidade)
+            .map(|_| recupera_posicao(&roleta, aleatorio.intervalo(0.0, 1.0)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn deve_concentrar_selecao_ao_esfriar() {
+        let seletor = SelecaoBoltzmann::criar(100.0, 0.5);
+        assert_eq!(100.0, seletor.temperatura(0));
+        assert_eq!(25.0, seletor.temperatura(2));
+        assert_eq!(0.001, seletor.temperatura(100));
+
+        let mut aleatorio = Aleatorio::semente(11);
+        let mut populacao = cria_populacao(&vec![0, 1, 2, 3, 4], Operacao::Min);
+
+        let pais = seletor.seleciona_pais(&populacao, 100, &mut aleatorio);
+        assert!(pais.iter().any(|pai| *pai != 0));
+
+        populacao.definir_geracao(20);
+        let pais = seletor.seleciona_pais(&populacao, 100, &mut aleatorio);
+        assert!(pais.iter().all(|pai| *pai == 0));
+    }
+}
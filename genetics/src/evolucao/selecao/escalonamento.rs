@@ -0,0 +1,262 @@
+//! # Escalonamento
+//!
+//! A seleção proporcional precisa de valores não negativos onde maior é melhor. As aptidões
+//! brutas nem sempre são assim: podem ser negativas e, na minimização, o melhor indivíduo tem a
+//! menor aptidão. O escalonamento transforma as aptidões em pesos de seleção, corrigindo esses
+//! casos e controlando a pressão de seleção.
+
+use populacao::Operacao;
+
+/// Transforma as aptidões de uma população em pesos de seleção.
+pub trait Escalonamento {
+    /// Devolve um peso não negativo para cada aptidão, na mesma ordem. Quanto maior o peso maior
+    /// a chance do indivíduo ser selecionado.
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64>;
+}
+
+/// Aptidões em que maior é sempre melhor, invertendo o sinal na minimização.
+pub fn orientar(aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+    match *operacao {
+        Operacao::Max => aptidoes.to_vec(),
+        Operacao::Min => aptidoes.iter().map(|a| -a).collect(),
+    }
+}
+
+fn minimo(valores: &[f64]) -> f64 {
+    valores.iter().cloned().fold(f64::INFINITY, f64::min)
+}
+
+fn media(valores: &[f64]) -> f64 {
+    valores.iter().sum::<f64>() / valores.len() as f64
+}
+
+/// Usa a aptidão como peso. Quando existem valores negativos, ou na minimização, os valores são
+/// deslocados para que o pior indivíduo tenha peso zero.
+pub struct EscalonamentoDireto;
+
+impl Escalonamento for EscalonamentoDireto {
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+        let valores = orientar(aptidoes, operacao);
+        let menor = minimo(&valores);
+        if menor < 0.0 {
+            valores.iter().map(|v| v - menor).collect()
+        } else {
+            valores
+        }
+    }
+}
+
+/// Escalonamento linear de Goldberg: o peso médio é mantido e o melhor indivíduo recebe
+/// *multiplicador* vezes o peso médio. Quando isso deixaria pesos negativos a reta é ajustada
+/// para que o pior indivíduo tenha peso zero. Assim como no `EscalonamentoDireto`, valores
+/// negativos, ou a minimização, são deslocados antes para que o pior indivíduo tenha valor zero.
+pub struct EscalonamentoLinear {
+    /// Quantas vezes o peso médio o melhor indivíduo recebe, normalmente entre 1.2 e 2
+    pub multiplicador: f64,
+}
+
+impl Escalonamento for EscalonamentoLinear {
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+        let mut valores = orientar(aptidoes, operacao);
+        let mut menor = minimo(&valores);
+        if menor < 0.0 {
+            valores = valores.iter().map(|v| v - menor).collect();
+            menor = 0.0;
+        }
+        let maior = valores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let media = media(&valores);
+
+        if maior - media <= 0.0 {
+            return vec![1.0; valores.len()];
+        }
+
+        let (a, b) = if menor > (self.multiplicador * media - maior) /
+                               (self.multiplicador - 1.0) {
+            let delta = maior - media;
+            ((self.multiplicador - 1.0) * media / delta,
+             media * (maior - self.multiplicador * media) / delta)
+        } else {
+            let delta = media - menor;
+            (media / delta, -menor * media / delta)
+        };
+
+        valores.iter().map(|v| (a * v + b).max(0.0)).collect()
+    }
+}
+
+/// Truncamento sigma: o peso é a distância acima de média - *c* desvios padrão. Indivíduos
+/// abaixo desse valor recebem peso zero.
+pub struct TruncamentoSigma {
+    /// Quantidade de desvios padrão abaixo da média, normalmente entre 1 e 3
+    pub c: f64,
+}
+
+impl Escalonamento for TruncamentoSigma {
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+        let valores = orientar(aptidoes, operacao);
+        let media = media(&valores);
+        let desvio = if valores.len() > 1 {
+            (valores.iter().map(|v| (v - media).powi(2)).sum::<f64>() /
+             (valores.len() - 1) as f64)
+                .sqrt()
+        } else {
+            0.0
+        };
+
+        let base = media - self.c * desvio;
+        valores.iter().map(|v| (v - base).max(0.0)).collect()
+    }
+}
+
+/// Janelamento: o peso é a distância para o pior indivíduo da população somada a *margem*, para
+/// que o pior ainda tenha alguma chance.
+pub struct Janelamento {
+    pub margem: f64,
+}
+
+impl Escalonamento for Janelamento {
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+        let valores = orientar(aptidoes, operacao);
+        let menor = minimo(&valores);
+        valores.iter().map(|v| v - menor + self.margem).collect()
+    }
+}
+
+/// Lei de potência: o peso é a distância para o pior indivíduo elevada a *expoente*. Expoentes
+/// maiores que 1 aumentam a pressão de seleção.
+pub struct EscalonamentoPotencia {
+    pub expoente: f64,
+}
+
+impl Escalonamento for EscalonamentoPotencia {
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+        let valores = orientar(aptidoes, operacao);
+        let menor = minimo(&valores);
+        valores.iter().map(|v| (v - menor).powf(self.expoente)).collect()
+    }
+}
+
+/// Escalonamento por posição: o peso depende apenas da posição do indivíduo na ordem das
+/// aptidões. O pior recebe 2 - *pressao* e o melhor recebe *pressao*.
+pub struct EscalonamentoPosicao {
+    /// Pressão de seleção entre 1 (sem pressão) e 2
+    pub pressao: f64,
+}
+
+impl Escalonamento for EscalonamentoPosicao {
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+        let qtd = aptidoes.len();
+        if qtd < 2 {
+            return vec![1.0; qtd];
+        }
+
+        let mut pesos = vec![0.0; qtd];
+        for (posicao, idx) in ordem_crescente(aptidoes, operacao).into_iter().enumerate() {
+            pesos[idx] = 2.0 - self.pressao +
+                         2.0 * (self.pressao - 1.0) * posicao as f64 / (qtd - 1) as f64;
+        }
+        pesos
+    }
+}
+
+/// Escalonamento exponencial por posição: o melhor indivíduo recebe peso 1, o segundo *base*, o
+/// terceiro *base*² e assim por diante.
+pub struct EscalonamentoPosicaoExponencial {
+    /// Base entre 0 e 1, quanto menor maior a pressão de seleção
+    pub base: f64,
+}
+
+impl Escalonamento for EscalonamentoPosicaoExponencial {
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+        let qtd = aptidoes.len();
+        let mut pesos = vec![0.0; qtd];
+        for (posicao, idx) in ordem_crescente(aptidoes, operacao).into_iter().enumerate() {
+            pesos[idx] = self.base.powi((qtd - 1 - posicao) as i32);
+        }
+        pesos
+    }
+}
+
+/// Escalonamento de Boltzmann: o peso é exp(aptidão / *temperatura*). Temperaturas altas deixam
+/// os pesos parecidos, temperaturas baixas concentram a seleção nos melhores indivíduos.
+pub struct EscalonamentoBoltzmann {
+    pub temperatura: f64,
+}
+
+impl Escalonamento for EscalonamentoBoltzmann {
+    fn escalonar(&self, aptidoes: &[f64], operacao: &Operacao) -> Vec<f64> {
+        let valores = orientar(aptidoes, operacao);
+        // Subtrair o maior valor não muda as proporções e evita estouro na exponencial
+        let maior = valores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        valores.iter().map(|v| ((v - maior) / self.temperatura).exp()).collect()
+    }
+}
+
+/// Índices das aptidões ordenados do pior para o melhor indivíduo.
+fn ordem_crescente(aptidoes: &[f64], operacao: &Operacao) -> Vec<usize> {
+    let mut ordem: Vec<usize> = (0..aptidoes.len()).collect();
+    ordem.sort_by(|a, b| {
+        operacao.compara(&aptidoes[*a], &aptidoes[*b])
+    });
+    ordem
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const ERRO_ACEITO: f64 = 0.00000001;
+
+    fn valida(esperado: &[f64], pesos: Vec<f64>) {
+        assert_eq!(esperado.len(), pesos.len());
+        for (e, p) in esperado.iter().zip(pesos.iter()) {
+            assert!((e - p).abs() < ERRO_ACEITO, "{:?} != {:?}", esperado, pesos);
+        }
+    }
+
+    #[test]
+    fn deve_orientar_minimizacao_e_negativos() {
+        valida(&[1.0, 2.0, 3.0],
+               EscalonamentoDireto.escalonar(&[1.0, 2.0, 3.0], &Operacao::Max));
+        valida(&[0.0, 1.0, 3.0],
+               EscalonamentoDireto.escalonar(&[-3.0, -2.0, 0.0], &Operacao::Max));
+        valida(&[2.0, 1.0, 0.0],
+               EscalonamentoDireto.escalonar(&[1.0, 2.0, 3.0], &Operacao::Min));
+    }
+
+    #[test]
+    fn deve_escalonar_linear() {
+        // A média é mantida e o melhor recebe 1.5 vezes a média
+        let linear = EscalonamentoLinear { multiplicador: 1.5 };
+        valida(&[1.5, 3.0, 4.5], linear.escalonar(&[2.0, 3.0, 4.0], &Operacao::Max));
+
+        // Com pesos negativos a reta é ajustada para que o pior receba zero
+        let linear = EscalonamentoLinear { multiplicador: 2.0 };
+        valida(&[0.0, 9.0, 10.0], linear.escalonar(&[0.0, 9.0, 10.0], &Operacao::Max));
+
+        // Na minimização e com aptidões negativas o pior é deslocado para zero antes
+        let linear = EscalonamentoLinear { multiplicador: 1.5 };
+        valida(&[1.5, 1.0, 0.5], linear.escalonar(&[1.0, 2.0, 3.0], &Operacao::Min));
+        valida(&[0.5, 1.0, 1.5], linear.escalonar(&[-3.0, -2.0, -1.0], &Operacao::Max));
+    }
+
+    #[test]
+    fn deve_escalonar_sigma_janela_potencia_posicao() {
+        let aptidoes = [-1.0, 0.0, 1.0];
+        valida(&[0.0, 1.0, 2.0],
+               TruncamentoSigma { c: 1.0 }.escalonar(&aptidoes, &Operacao::Max));
+        valida(&[0.5, 1.5, 2.5],
+               Janelamento { margem: 0.5 }.escalonar(&aptidoes, &Operacao::Max));
+        valida(&[4.0, 1.0, 0.0],
+               EscalonamentoPotencia { expoente: 2.0 }.escalonar(&aptidoes, &Operacao::Min));
+        valida(&[0.5, 1.0, 1.5],
+               EscalonamentoPosicao { pressao: 1.5 }.escalonar(&[10.0, 20.0, 30.0],
+                                                                &Operacao::Max));
+        valida(&[1.0, 0.25, 0.5],
+               EscalonamentoPosicaoExponencial { base: 0.5 }.escalonar(&[10.0, 30.0, 20.0],
+                                                                        &Operacao::Min));
+        valida(&[(-4.0f64).exp(), (-2.0f64).exp(), 1.0],
+               EscalonamentoBoltzmann { temperatura: 0.5 }.escalonar(&aptidoes, &Operacao::Max));
+    }
+}
@@ -2,18 +2,137 @@
 
 pub mod torneio;
 pub mod roleta;
+pub mod escalonamento;
+pub mod ranking;
+pub mod truncamento;
+pub mod boltzmann;
 
 // Reexportando os tipos desse módulo para facilitar a vida.
+pub use self::escalonamento::*;
 pub use self::roleta::*;
 pub use self::torneio::*;
+pub use self::ranking::*;
+pub use self::truncamento::*;
+pub use self::boltzmann::*;
 
 use populacao::*;
+use aleatorio::Aleatorio;
 
 
 /// Interface que define as várias estratégias de seleção de individuos para o
 /// cruzamento.
 pub trait Seletor<Gene> {
-    /// Implementações desse método deverão selecionar dois individuos para
-    /// cruzamento, removendo esses indivíduos da população
-    fn seleciona(&self, pop: &mut Populacao<Gene>) -> Option<(Individuo<Gene>, Individuo<Gene>)>;
+    /// Seleciona *quantidade* indivíduos de *pop*, devolvendo os seus índices em
+    /// `pop.individuos`. A população não é alterada e o mesmo indivíduo pode ser selecionado
+    /// mais de uma vez.
+    fn seleciona_pais(&self,
+                      pop: &Populacao<Gene>,
+                      quantidade: usize,
+                      aleatorio: &mut Aleatorio)
+                      -> Vec<usize>;
+
+    /// Seleciona os pais de *grupos* cruzamentos com *pais_por_grupo* pais cada, por exemplo
+    /// para cruzamentos com mais de dois pais.
+    fn seleciona_grupos(&self,
+                        pop: &Populacao<Gene>,
+                        grupos: usize,
+                        pais_por_grupo: usize,
+                        aleatorio: &mut Aleatorio)
+                        -> Vec<Vec<usize>> {
+        if pais_por_grupo == 0 {
+            return vec![Vec::new(); grupos];
+        }
+
+        self.seleciona_pais(pop, grupos * pais_por_grupo, aleatorio)
+            .chunks(pais_por_grupo)
+            .filter(|grupo| grupo.len() == pais_por_grupo)
+            .map(|grupo| grupo.to_vec())
+            .collect()
+    }
+
+    /// Seleciona dois individuos para cruzamento, removendo esses indivíduos da população. Eles
+    /// devem ser devolvidos com `Populacao::reiterar` após o cruzamento.
+    ///
+    /// Mantido para quem usa a interface antiga, a implementação padrão seleciona os pais com
+    /// `seleciona_pais`.
+    fn seleciona(&self,
+                 pop: &mut Populacao<Gene>,
+                 aleatorio: &mut Aleatorio)
+                 -> Option<(Individuo<Gene>, Individuo<Gene>)> {
+        remove_pais(self, pop, aleatorio)
+    }
+}
+
+/// Permite escolher o seletor em tempo de execução.
+impl<Gene, S> Seletor<Gene> for Box<S>
+    where S: Seletor<Gene> + ?Sized
+{
+    fn seleciona_pais(&self,
+                      pop: &Populacao<Gene>,
+                      quantidade: usize,
+                      aleatorio: &mut Aleatorio)
+                      -> Vec<usize> {
+        (**self).seleciona_pais(pop, quantidade, aleatorio)
+    }
+
+    fn seleciona_grupos(&self,
+                        pop: &Populacao<Gene>,
+                        grupos: usize,
+                        pais_por_grupo: usize,
+                        aleatorio: &mut Aleatorio)
+                        -> Vec<Vec<usize>> {
+        (**self).seleciona_grupos(pop, grupos, pais_por_grupo, aleatorio)
+    }
+
+    fn seleciona(&self,
+                 pop: &mut Populacao<Gene>,
+                 aleatorio: &mut Aleatorio)
+                 -> Option<(Individuo<Gene>, Individuo<Gene>)> {
+        (**self).seleciona(pop, aleatorio)
+    }
+}
+
+/// Escolhe dois pais com `seleciona_pais` e os remove da população. Quando o seletor não
+/// devolve os dois pais nada é removido.
+fn remove_pais<Gene, S>(seletor: &S,
+                        pop: &mut Populacao<Gene>,
+                        aleatorio: &mut Aleatorio)
+                        -> Option<(Individuo<Gene>, Individuo<Gene>)>
+    where S: Seletor<Gene> + ?Sized
+{
+    if pop.individuos.len() < 2 {
+        return None;
+    }
+
+    let pais = seletor.seleciona_pais(pop, 2, aleatorio);
+    if pais.len() < 2 {
+        return None;
+    }
+    trace!("Selecionado para cruzamento {} {}", pais[0], pais[1]);
+    Some(remove_par(pop, pais[0], pais[1]))
+}
+
+/// Remove da população os dois indivíduos selecionados. Quando a mesma posição foi sorteada
+/// duas vezes um vizinho é usado como segundo indivíduo.
+fn remove_par<Gene>(pop: &mut Populacao<Gene>,
+                    posicao_primeiro: usize,
+                    mut posicao_segundo: usize)
+                    -> (Individuo<Gene>, Individuo<Gene>) {
+
+    let primeiro = pop.individuos.remove(posicao_primeiro);
+
+    if posicao_primeiro < posicao_segundo {
+        posicao_segundo -= 1;
+    }
+
+    if posicao_segundo == posicao_primeiro {
+        if posicao_segundo == 0 {
+            posicao_segundo = 1.min(pop.individuos.len() - 1);
+        } else {
+            posicao_segundo -= 1;
+        }
+    }
+    let segundo = pop.individuos.remove(posicao_segundo);
+
+    (primeiro, segundo)
 }
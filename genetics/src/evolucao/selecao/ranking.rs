@@ -0,0 +1,63 @@
+use populacao::*;
+use evolucao::selecao::Seletor;
+use evolucao::selecao::roleta::SeletorPorRoleta;
+use evolucao::selecao::escalonamento::{EscalonamentoPosicao, EscalonamentoPosicaoExponencial};
+use aleatorio::Aleatorio;
+
+/// Seleção por ranking: a chance de cada indivíduo depende apenas da sua posição na ordem das
+/// aptidões, não da diferença entre as aptidões. Evita que um indivíduo muito melhor que os
+/// outros domine a população logo nas primeiras gerações.
+pub struct SelecaoPorRanking {
+    roleta: SeletorPorRoleta,
+}
+
+impl SelecaoPorRanking {
+    /// Ranking linear, o melhor indivíduo tem *pressao* vezes a chance média de ser selecionado
+    /// e o pior tem 2 - *pressao* vezes. A pressão deve estar entre 1 e 2.
+    pub fn criar_linear(pressao: f64) -> Self {
+        SelecaoPorRanking {
+            roleta: SeletorPorRoleta::criar_com_escalonamento(EscalonamentoPosicao { pressao }),
+        }
+    }
+
+    /// Ranking exponencial, cada posição tem *base* vezes a chance da posição seguinte. A base
+    /// deve estar entre 0 e 1, quanto menor maior a pressão de seleção.
+    pub fn criar_exponencial(base: f64) -> Self {
+        let escalonamento = EscalonamentoPosicaoExponencial { base };
+        SelecaoPorRanking { roleta: SeletorPorRoleta::criar_com_escalonamento(escalonamento) }
+    }
+}
+
+impl<Gene> Seletor<Gene> for SelecaoPorRanking {
+    fn seleciona_pais(&self,
+                      pop: &Populacao<Gene>,
+                      quantidade: usize,
+                      aleatorio: &mut Aleatorio)
+                      -> Vec<usize> {
+        self.roleta.seleciona_pais(pop, quantidade, aleatorio)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn deve_selecionar_pela_posicao() {
+        let populacao = cria_populacao(&vec![0, 1, 2, 3, 4], Operacao::Min);
+        let mut aleatorio = Aleatorio::semente(3);
+
+        // Com pressão 2 o pior indivíduo nunca é selecionado
+        let pais = SelecaoPorRanking::criar_linear(2.0)
+                       .seleciona_pais(&populacao, 200, &mut aleatorio);
+        assert!(pais.iter().all(|pai| *pai != 4));
+
+        let pais = SelecaoPorRanking::criar_exponencial(0.5)
+                       .seleciona_pais(&populacao, 200, &mut aleatorio);
+        let contagem: Vec<usize> = (0..5)
+                                       .map(|idx| pais.iter().filter(|pai| **pai == idx).count())
+                                       .collect();
+        assert!(contagem[0] > contagem[1] && contagem[1] > contagem[3]);
+    }
+}
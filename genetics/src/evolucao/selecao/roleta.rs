@@ -2,16 +2,28 @@
 
 use populacao::*;
 use evolucao::selecao::Seletor;
+use evolucao::selecao::escalonamento::{Escalonamento, EscalonamentoDireto};
 
 use aleatorio::Aleatorio;
 
-pub struct SeletorPorRoleta;
+pub struct SeletorPorRoleta {
+    /// Transforma as aptidões nos pesos usados para montar a roleta
+    pub escalonamento: Box<dyn Escalonamento + Send + Sync>,
+}
 
 /// Implementação de seleção de indivíduos usando o método de roleta.
 impl SeletorPorRoleta {
-    /// Cria uma nova instância
+    /// Cria uma nova instância que usa a aptidão como peso, deslocada quando é negativa ou
+    /// invertida na minimização.
     pub fn criar() -> Self {
-        SeletorPorRoleta
+        SeletorPorRoleta::criar_com_escalonamento(EscalonamentoDireto)
+    }
+
+    /// Cria uma nova instância que monta a roleta com os pesos do escalonamento informado.
+    pub fn criar_com_escalonamento<E>(escalonamento: E) -> Self
+        where E: Escalonamento + Send + Sync + 'static
+    {
+        SeletorPorRoleta { escalonamento: Box::new(escalonamento) }
     }
 }
 
@@ -19,84 +31,133 @@ impl SeletorPorRoleta {
 #[derive(Debug)]
 pub struct Roleta {
     /// Indice do indivíduo
-    idx: usize,
+    pub idx: usize,
     /// Inicio da chance de ser sorteado
-    faixa_inicio: f64,
+    pub faixa_inicio: f64,
     /// Fim da chance de ser sorteado.
-    faixa_fim: f64,
+    pub faixa_fim: f64,
 }
 
 /// Implementação da seleção de indivíduos por roleta quando ele é usado como seletor.
 impl<Gene> Seletor<Gene> for SeletorPorRoleta {
-    fn seleciona(&self, pop: &mut Populacao<Gene>) -> Option<(Individuo<Gene>, Individuo<Gene>)> {
-
+    /// Monta a roleta uma única vez e sorteia cada pai com uma busca binária nas faixas.
+    fn seleciona_pais(&self,
+                      pop: &Populacao<Gene>,
+                      quantidade: usize,
+                      aleatorio: &mut Aleatorio)
+                      -> Vec<usize> {
+
+        if pop.individuos.is_empty() {
+            return Vec::new();
+        }
 
+        let roleta = gera_roleta(pop, &*self.escalonamento);
+        (0..quantidade)
+            .map(|_| recupera_posicao(&roleta, aleatorio.intervalo(0.0, 1.0)))
+            .collect()
+    }
+}
 
-        // Encontra os dois indivíduos que devem ser cruzados.
-        trace!("Executando seleção por roleta");
-        let (posicao_primeiro, mut posicao_segundo) = encontra_par(pop);
+/// Seleção universal estocástica: a roleta é girada uma única vez com tantos ponteiros
+/// igualmente espaçados quanto os pais necessários. Cada indivíduo é selecionado um número de
+/// vezes muito próximo do esperado pelo seu peso, sem o ruído de girar a roleta várias vezes.
+pub struct SelecaoUniversalEstocastica {
+    /// Transforma as aptidões nos pesos usados para montar a roleta
+    pub escalonamento: Box<dyn Escalonamento + Send + Sync>,
+}
 
+impl SelecaoUniversalEstocastica {
+    /// Cria uma nova instância que usa a aptidão como peso, deslocada quando é negativa ou
+    /// invertida na minimização.
+    pub fn criar() -> Self {
+        SelecaoUniversalEstocastica::criar_com_escalonamento(EscalonamentoDireto)
+    }
 
-        trace!("Selecionado para cruzamento {} {}",
-               posicao_primeiro,
-               posicao_segundo);
-        let primeiro = pop.individuos.remove(posicao_primeiro);
+    /// Cria uma nova instância que monta a roleta com os pesos do escalonamento informado.
+    pub fn criar_com_escalonamento<E>(escalonamento: E) -> Self
+        where E: Escalonamento + Send + Sync + 'static
+    {
+        SelecaoUniversalEstocastica { escalonamento: Box::new(escalonamento) }
+    }
+}
 
-        if posicao_primeiro < posicao_segundo {
-            posicao_segundo = posicao_segundo - 1;
-        }
+impl<Gene> Seletor<Gene> for SelecaoUniversalEstocastica {
+    /// Seleciona todos os pais da geração girando a roleta uma única vez.
+    fn seleciona_pais(&self,
+                      pop: &Populacao<Gene>,
+                      quantidade: usize,
+                      aleatorio: &mut Aleatorio)
+                      -> Vec<usize> {
 
-        if posicao_segundo == posicao_primeiro {
-            if posicao_segundo == 0 {
-                posicao_segundo = 1
-            } else {
-                posicao_segundo -= 1;
-            }
+        if pop.individuos.is_empty() {
+            return Vec::new();
         }
-        let segundo = pop.individuos.remove(posicao_segundo);
 
-        Some((primeiro, segundo))
+        let roleta = gera_roleta(pop, &*self.escalonamento);
+        amostra_universal(&roleta, quantidade, aleatorio)
     }
 }
 
+/// Percorre a roleta uma única vez com *quantidade* ponteiros igualmente espaçados. Os
+/// selecionados são embaralhados, senão pais consecutivos seriam quase sempre vizinhos na roleta.
+fn amostra_universal(roleta: &[Roleta],
+                     quantidade: usize,
+                     aleatorio: &mut Aleatorio)
+                     -> Vec<usize> {
+    let mut selecionados = Vec::with_capacity(quantidade);
+    if quantidade == 0 {
+        return selecionados;
+    }
 
-fn encontra_par<Gene>(pop: &Populacao<Gene>) -> (usize, usize) {
-
-
-    let roleta = gera_roleta(&pop);
-
-    let mut aleatorio = Aleatorio::criar();
-    let chance_primeiro = aleatorio.intervalo(0.0, 1.0);
-    let chance_segundo = aleatorio.intervalo(0.0, 1.0);
-
-    let posicao_primeiro = recupera_posicao(&roleta, chance_primeiro);
-    let posicao_segundo = recupera_posicao(&roleta, chance_segundo);
+    let passo = 1.0 / quantidade as f64;
+    let inicio = aleatorio.intervalo(0.0, passo);
+    let mut atual = 0;
+    for i in 0..quantidade {
+        let ponteiro = inicio + i as f64 * passo;
+        while atual + 1 < roleta.len() && roleta[atual].faixa_fim <= ponteiro {
+            atual += 1;
+        }
+        selecionados.push(roleta[atual].idx);
+    }
 
-    (posicao_primeiro, posicao_segundo)
+    aleatorio.embaralhar(&mut selecionados);
+    selecionados
 }
 
+/// Recupera o indivíduo sorteado com uma busca binária nas faixas. Arredondamentos podem fazer a
+/// última faixa terminar um pouco antes de 1, nesse caso o último indivíduo é escolhido.
+#[allow(clippy::ptr_arg)]
 pub fn recupera_posicao(roleta: &Vec<Roleta>, chance: f64) -> usize {
-    roleta.iter()
-          .find(|roleta| roleta.faixa_inicio <= chance && roleta.faixa_fim > chance)
+    let posicao = roleta.partition_point(|roleta| roleta.faixa_fim <= chance);
+    roleta.get(posicao)
+          .or_else(|| roleta.last())
           .map(|roleta| roleta.idx)
-          .unwrap()
+          .unwrap_or(0)
 }
 
-/// Gera a roleta com base no aptidão da população
-pub fn gera_roleta<Gene>(pop: &Populacao<Gene>) -> Vec<Roleta> {
-
-    // Encontra o valor toda da aptidão dos indivíduos para distribuir na roleta
-    let aptidao_total = pop.individuos
-                           .iter()
-                           .map(|ind| ind.aptidao)
-                           .fold(0.0, |a, b| a + b);
-    trace!("Aptidão total: {}", aptidao_total);
+/// Gera a roleta com base nos pesos que o escalonamento atribui à aptidão da população
+#[allow(clippy::redundant_field_names)]
+pub fn gera_roleta<Gene>(pop: &Populacao<Gene>,
+                         escalonamento: &dyn Escalonamento)
+                         -> Vec<Roleta> {
+
+    let aptidoes: Vec<f64> = pop.individuos.iter().map(|ind| ind.aptidao).collect();
+    let mut pesos = escalonamento.escalonar(&aptidoes, pop.operacao());
+
+    // Encontra o valor total dos pesos dos indivíduos para distribuir na roleta
+    let mut peso_total = pesos.iter().fold(0.0, |a, b| a + b);
+    if !(peso_total > 0.0 && peso_total.is_finite()) {
+        // Todos os pesos zerados, todos têm a mesma chance
+        pesos = vec![1.0; pesos.len()];
+        peso_total = pesos.len() as f64;
+    }
+    trace!("Peso total: {}", peso_total);
 
     // Monta a roleta
     let mut roleta = Vec::new();
     let mut inicio = 0.0;
-    for (idx, ind) in pop.individuos.iter().enumerate() {
-        let participacao = inicio + ind.aptidao / aptidao_total;
+    for (idx, peso) in pesos.iter().enumerate() {
+        let participacao = inicio + peso / peso_total;
         roleta.push(Roleta {
             idx: idx,
             faixa_inicio: inicio,
@@ -115,13 +176,12 @@ pub fn gera_roleta<Gene>(pop: &Populacao<Gene>) -> Vec<Roleta> {
 mod test {
 
     use super::*;
-    use populacao::*;
 
     const ERRO_ACEITO: f64 = 0.00000001;
 
     fn gera_roleta_teste() -> Vec<Roleta> {
         let populacao = cria_populacao(&vec![0, 1, 2, 3, 4], Operacao::Max);
-        gera_roleta(&populacao)
+        gera_roleta(&populacao, &EscalonamentoDireto)
     }
 
     #[test]
@@ -156,6 +216,39 @@ mod test {
         let indice = recupera_posicao(&roleta, 0.5);
 
         assert_eq!(indice, 3);
+        assert_eq!(recupera_posicao(&roleta, 1.0), 4);
+    }
+
+    #[test]
+    fn deve_favorecer_menor_aptidao_na_minimizacao() {
+        let populacao = cria_populacao(&vec![0, 1, 2, 3, 4], Operacao::Min);
+        let roleta = gera_roleta(&populacao, &EscalonamentoDireto);
+
+        // Pesos 4, 3, 2, 1 e 0
+        assert!((roleta[0].faixa_fim - 0.4).abs() < ERRO_ACEITO);
+        assert!((roleta[3].faixa_inicio - 0.9).abs() < ERRO_ACEITO);
+        assert!((roleta[4].faixa_fim - 1.0).abs() < ERRO_ACEITO);
+        assert_eq!(recupera_posicao(&roleta, 0.1), 0);
+        assert_eq!(recupera_posicao(&roleta, 0.95), 3);
+    }
+
+    #[test]
+    fn deve_selecionar_pais_proporcionalmente_ao_peso() {
+        let populacao = cria_populacao(&vec![0, 1, 2, 3, 4], Operacao::Max);
+        let mut aleatorio = Aleatorio::semente(7);
+
+        // Com 10 ponteiros e pesos 0, 1, 2, 3 e 4 cada indivíduo aparece exatamente o número de
+        // vezes do seu peso
+        let pais = SelecaoUniversalEstocastica::criar()
+                       .seleciona_pais(&populacao, 10, &mut aleatorio);
+        for idx in 0..5 {
+            assert_eq!(idx, pais.iter().filter(|pai| **pai == idx).count());
+        }
+
+        let pais = SeletorPorRoleta::criar().seleciona_pais(&populacao, 100, &mut aleatorio);
+        assert_eq!(100, pais.len());
+        assert!(pais.iter().all(|pai| *pai != 0));
+        assert_eq!(5, populacao.individuos.len());
     }
 
 }
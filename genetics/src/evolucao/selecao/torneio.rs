@@ -1,4 +1,7 @@
 
+use std::error::Error;
+use std::fmt;
+
 use populacao::*;
 use evolucao::selecao::Seletor;
 use aleatorio::Aleatorio;
@@ -6,45 +9,265 @@ use aleatorio::Aleatorio;
 
 /// Classe que faz seleção de indivíduos para cruzamento usando a técnica de torneio.
 pub struct SelecaoPorTorneio {
-    participantes: usize, // Quantidade de perticipantes no torneio
+    /// Quantidade de perticipantes no torneio
+    participantes: usize,
+    /// Permite que o mesmo indivíduo participe mais de uma vez do mesmo torneio
+    reposicao: bool,
+    /// Chance, entre 0 e 1, do participante mais apto vencer o torneio. Quando ele não vence a
+    /// mesma chance é aplicada ao segundo mais apto e assim por diante.
+    chance_vitoria: f64,
+    /// Seleciona o perdedor do torneio em vez do vencedor, por exemplo para escolher quem será
+    /// substituído na população
+    perdedor: bool,
 }
 
+/// Parametros inválidos para o torneio.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErroTorneio {
+    /// O torneio precisa de pelo menos um participante
+    SemParticipantes,
+    /// A chance de vitória deve estar entre 0 e 1
+    ChanceVitoriaInvalida(f64),
+    /// Sem reposição o torneio não pode ter mais participantes que a população
+    PopulacaoMenorQueTorneio { individuos: usize, participantes: usize },
+}
 
-impl SelecaoPorTorneio {
-    pub fn criar(participantes: usize) -> Self {
-        SelecaoPorTorneio { participantes: participantes }
+impl fmt::Display for ErroTorneio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErroTorneio::SemParticipantes => write!(f, "Torneio sem participantes"),
+            ErroTorneio::ChanceVitoriaInvalida(chance) => {
+                write!(f, "Chance de vitória({}) fora do intervalo de 0 a 1", chance)
+            }
+            ErroTorneio::PopulacaoMenorQueTorneio { individuos, participantes } => {
+                write!(f,
+                       "Quantidade de individuos({}) menor que o torneio({})",
+                       individuos,
+                       participantes)
+            }
+        }
     }
 }
 
-impl<Gene> Seletor<Gene> for SelecaoPorTorneio {
-    fn seleciona(&self, pop: &mut Populacao<Gene>) -> Option<(Individuo<Gene>, Individuo<Gene>)> {
+impl Error for ErroTorneio {}
 
-        trace!("Executando seleção por torneio");
-        if pop.individuos.len() < 2 {
-            return None;
+
+impl SelecaoPorTorneio {
+    /// Cria um torneio determinístico, sem reposição, em que o mais apto sempre vence. O
+    /// torneio precisa de pelo menos um participante.
+    pub fn criar(participantes: usize) -> Result<Self, ErroTorneio> {
+        if participantes == 0 {
+            return Err(ErroTorneio::SemParticipantes);
         }
 
-        if pop.individuos.len() < self.participantes {
-            panic!("Quantidade de individuos({}) menor que o torneio({})",
-                   pop.individuos.len(),
-                   self.participantes);
+        Ok(SelecaoPorTorneio {
+            participantes,
+            reposicao: false,
+            chance_vitoria: 1.0,
+            perdedor: false,
+        })
+    }
+
+    /// Permite que o mesmo indivíduo participe mais de uma vez do mesmo torneio, o que permite
+    /// torneios maiores que a população.
+    pub fn reposicao(&mut self, reposicao: bool) {
+        self.reposicao = reposicao;
+    }
+
+    /// Define a chance, entre 0 e 1, do participante mais apto vencer o torneio. Quando ele não
+    /// vence a mesma chance é aplicada ao segundo mais apto e assim por diante.
+    pub fn chance_vitoria(&mut self, chance_vitoria: f64) -> Result<(), ErroTorneio> {
+        if !(0.0..=1.0).contains(&chance_vitoria) {
+            return Err(ErroTorneio::ChanceVitoriaInvalida(chance_vitoria));
         }
+        self.chance_vitoria = chance_vitoria;
+        Ok(())
+    }
 
-        let mut torneio = pop.preparar_nova_geracao();
-        let mut aleatorio = Aleatorio::criar();
-        for _ in 0..self.participantes {
+    /// Seleciona o perdedor do torneio em vez do vencedor, por exemplo para escolher quem será
+    /// substituído na população.
+    pub fn perdedor(&mut self, perdedor: bool) {
+        self.perdedor = perdedor;
+    }
+
+    /// Verifica se o torneio pode ser usado com uma população de *tamanho_populacao*
+    /// indivíduos: sem reposição a população não pode ser menor que o torneio.
+    pub fn validar(&self, tamanho_populacao: usize) -> Result<(), ErroTorneio> {
+        if !self.reposicao && tamanho_populacao < self.participantes {
+            return Err(ErroTorneio::PopulacaoMenorQueTorneio {
+                individuos: tamanho_populacao,
+                participantes: self.participantes,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Seleciona os índices de *quantidade* indivíduos, cada um o resultado de um torneio.
+    /// Devolve um erro quando o torneio não pode ser usado com essa população.
+    pub fn selecionar<Gene>(&self,
+                            pop: &Populacao<Gene>,
+                            quantidade: usize,
+                            aleatorio: &mut Aleatorio)
+                            -> Result<Vec<usize>, ErroTorneio> {
+
+        if pop.individuos.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.validar(pop.individuos.len())?;
+
+        let mut torneio = Vec::with_capacity(self.participantes);
+        Ok((0..quantidade)
+               .map(|_| {
+                   self.sorteia_participantes(&mut torneio, pop, aleatorio);
+                   self.resultado(&mut torneio, pop, aleatorio)
+               })
+               .collect())
+    }
+
+    /// Sorteia em *torneio* os índices dos participantes.
+    fn sorteia_participantes<Gene>(&self,
+                                   torneio: &mut Vec<usize>,
+                                   pop: &Populacao<Gene>,
+                                   aleatorio: &mut Aleatorio) {
+        torneio.clear();
+        while torneio.len() < self.participantes {
             let idx = aleatorio.intervalo(0, pop.individuos.len());
+            if self.reposicao || !torneio.contains(&idx) {
+                torneio.push(idx);
+            }
+        }
+    }
+
+    /// Escolhe o vencedor, ou o perdedor, entre os participantes do torneio.
+    fn resultado<Gene>(&self,
+                       torneio: &mut [usize],
+                       pop: &Populacao<Gene>,
+                       aleatorio: &mut Aleatorio)
+                       -> usize {
 
-            let individuo = pop.individuos.remove(idx);
+        // Ordena do participante que tem mais chance de ser escolhido para o que tem menos
+        torneio.sort_by(|a, b| {
+            let ordem = pop.operacao()
+                           .compara(&pop.individuos[*b].aptidao, &pop.individuos[*a].aptidao);
+            if self.perdedor { ordem.reverse() } else { ordem }
+        });
 
-            torneio.reiterar(individuo);
+        if self.chance_vitoria < 1.0 {
+            for idx in torneio[..torneio.len() - 1].iter() {
+                if aleatorio.chance() < self.chance_vitoria {
+                    return *idx;
+                }
+            }
+            return torneio[torneio.len() - 1];
         }
+        torneio[0]
+    }
+}
+
+impl<Gene> Seletor<Gene> for SelecaoPorTorneio {
+    /// Cada pai é o resultado de um torneio. Um torneio sem reposição maior que a população
+    /// interrompe a execução com o erro, use `validar` para verificar o tamanho da população
+    /// antes da execução ou `selecionar` para tratar o erro.
+    fn seleciona_pais(&self,
+                      pop: &Populacao<Gene>,
+                      quantidade: usize,
+                      aleatorio: &mut Aleatorio)
+                      -> Vec<usize> {
+
+        trace!("Executando seleção por torneio");
+        match self.selecionar(pop, quantidade, aleatorio) {
+            Ok(pais) => pais,
+            Err(erro) => panic!("Seleção por torneio: {}", erro),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use evolucao::Evolucao;
+    use evolucao::cruzamento::{Cruzador, Cruzamento};
 
-        let primeiro = torneio.remover_mais_apto();
-        let segundo = torneio.remover_mais_apto();
+    #[test]
+    fn deve_selecionar_sem_alterar_a_populacao() {
+        let populacao = cria_populacao(&vec![3, 0, 4, 1, 2], Operacao::Min);
+        let mut aleatorio = Aleatorio::semente(1);
 
-        pop.individuos.append(&mut torneio.individuos);
+        // Com todos os indivíduos no torneio o vencedor é sempre o mais apto
+        let torneio = SelecaoPorTorneio::criar(5).unwrap();
+        let pais = torneio.seleciona_pais(&populacao, 6, &mut aleatorio);
+        assert_eq!(vec![1; 6], pais);
+
+        let grupos = SelecaoPorTorneio::criar(2)
+                         .unwrap()
+                         .seleciona_grupos(&populacao, 4, 3, &mut aleatorio);
+        assert_eq!(4, grupos.len());
+        assert!(grupos.iter().all(|grupo| grupo.len() == 3));
+        // O pior indivíduo nunca vence um torneio
+        assert!(grupos.iter().flat_map(|grupo| grupo.iter()).all(|idx| *idx != 2));
+
+        let genes: Vec<usize> = populacao.individuos.iter().map(|ind| ind.genes).collect();
+        assert_eq!(vec![3, 0, 4, 1, 2], genes);
+    }
 
-        Some((primeiro, segundo))
+    #[test]
+    fn deve_configurar_o_torneio() {
+        let populacao = cria_populacao(&vec![3, 0, 4, 1, 2], Operacao::Min);
+        let mut aleatorio = Aleatorio::semente(2);
+
+        let mut torneio = SelecaoPorTorneio::criar(5).unwrap();
+        torneio.perdedor(true);
+        assert_eq!(Ok(vec![2; 4]), torneio.selecionar(&populacao, 4, &mut aleatorio));
+
+        // Com chance de vitória menor que 1 o mais apto nem sempre vence
+        torneio.perdedor(false);
+        torneio.chance_vitoria(0.5).unwrap();
+        let pais = torneio.selecionar(&populacao, 50, &mut aleatorio).unwrap();
+        assert!(pais.contains(&1));
+        assert!(pais.iter().any(|pai| *pai != 1));
+
+        // Com reposição o torneio pode ser maior que a população
+        let mut torneio = SelecaoPorTorneio::criar(8).unwrap();
+        assert_eq!(Err(ErroTorneio::PopulacaoMenorQueTorneio {
+                       individuos: 5,
+                       participantes: 8,
+                   }),
+                   torneio.selecionar(&populacao, 1, &mut aleatorio));
+        torneio.reposicao(true);
+        assert!(torneio.selecionar(&populacao, 1, &mut aleatorio).is_ok());
+    }
+
+    #[test]
+    fn deve_rejeitar_parametros_invalidos() {
+        assert_eq!(Some(ErroTorneio::SemParticipantes),
+                   SelecaoPorTorneio::criar(0).err());
+
+        let mut torneio = SelecaoPorTorneio::criar(2).unwrap();
+        assert_eq!(Err(ErroTorneio::ChanceVitoriaInvalida(1.5)), torneio.chance_vitoria(1.5));
+        assert_eq!(Err(ErroTorneio::ChanceVitoriaInvalida(-0.1)), torneio.chance_vitoria(-0.1));
+        assert_eq!(1.0, torneio.chance_vitoria);
+    }
+
+    #[test]
+    #[should_panic(expected = "Quantidade de individuos(3) menor que o torneio(5)")]
+    fn torneio_maior_que_a_populacao_deve_interromper_o_cruzamento() {
+        let mut populacao = cria_populacao(&vec![3, 0, 4], Operacao::Max);
+        let mut aleatorio = Aleatorio::semente(3);
+
+        // O cruzamento não pode seguir sem gerar filhos
+        let cruzamento = Cruzamento::criar(SelecaoPorTorneio::criar(5).unwrap(),
+                                           CruzadorSoma,
+                                           1.0);
+        cruzamento.evoluir(&mut populacao, &mut aleatorio);
+    }
+
+    struct CruzadorSoma;
+
+    impl Cruzador<usize> for CruzadorSoma {
+        fn cruzar(&self, _: &mut Aleatorio, primeiro: &usize, segundo: &usize) -> (usize, usize) {
+            (primeiro + segundo, primeiro + segundo)
+        }
     }
 }
@@ -0,0 +1,59 @@
+use populacao::*;
+use evolucao::selecao::Seletor;
+use aleatorio::Aleatorio;
+
+/// Seleção por truncamento: apenas a fração mais apta da população participa do cruzamento,
+/// todos com a mesma chance.
+pub struct SelecaoPorTruncamento {
+    /// Fração da população, entre 0 e 1, que pode ser selecionada
+    pub proporcao: f64,
+}
+
+impl SelecaoPorTruncamento {
+    pub fn criar(proporcao: f64) -> Self {
+        SelecaoPorTruncamento { proporcao }
+    }
+}
+
+impl<Gene> Seletor<Gene> for SelecaoPorTruncamento {
+    fn seleciona_pais(&self,
+                      pop: &Populacao<Gene>,
+                      quantidade: usize,
+                      aleatorio: &mut Aleatorio)
+                      -> Vec<usize> {
+
+        let qtd = pop.individuos.len();
+        if qtd == 0 {
+            return Vec::new();
+        }
+
+        // Separa os mais aptos no começo da lista, sem precisar ordenar toda a população
+        let selecionaveis = ((qtd as f64 * self.proporcao).ceil() as usize).max(1).min(qtd);
+        let mut ordem: Vec<usize> = (0..qtd).collect();
+        ordem.select_nth_unstable_by(selecionaveis - 1, |a, b| {
+            pop.operacao().compara(&pop.individuos[*b].aptidao, &pop.individuos[*a].aptidao)
+        });
+
+        (0..quantidade).map(|_| ordem[aleatorio.intervalo(0, selecionaveis)]).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn deve_selecionar_apenas_os_mais_aptos() {
+        let mut aleatorio = Aleatorio::semente(5);
+        let seletor = SelecaoPorTruncamento::criar(0.3);
+
+        let populacao = cria_populacao(&vec![5, 0, 9, 3, 7, 1, 8, 2, 6, 4], Operacao::Max);
+        let pais = seletor.seleciona_pais(&populacao, 50, &mut aleatorio);
+        assert!(pais.iter().all(|pai| populacao.individuos[*pai].genes >= 7));
+
+        let populacao = cria_populacao(&vec![5, 0, 9, 3, 7, 1, 8, 2, 6, 4], Operacao::Min);
+        let pais = seletor.seleciona_pais(&populacao, 50, &mut aleatorio);
+        assert!(pais.iter().all(|pai| populacao.individuos[*pai].genes <= 2));
+    }
+}
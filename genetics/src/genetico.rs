@@ -1,8 +1,20 @@
 
+use std::io;
+use std::mem;
+use std::time::{Duration, Instant};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
 use evolucao::*;
 use populacao::*;
 use objetivo::*;
 use observador::*;
+use aleatorio::Aleatorio;
+use salvamento::EstadoGenetico;
+use resultado::*;
 
 
 /// Implementação do algoritmo genetico simplificado. Ela está dessa forma para
@@ -19,9 +31,51 @@ pub struct Genetico<Gene, Evol, Obj>
     /// O objetivo que se deseja atingir
     pub objetivo: Obj,
     /// O observador das evoluções
-    pub observador: Box<ObservadorEvolucao<Gene>>,
+    pub observador: Box<dyn ObservadorEvolucao<Gene>>,
     /// Informação se deve ou não ser utilizado elitismo entre as gerações.
     pub elitismo: bool,
+    /// Gerador de números aleatórios usado em todas as evoluções
+    pub aleatorio: Aleatorio,
+    /// Quantidade de gerações já executadas
+    pub geracao: usize,
+    /// Quantidade de indivíduos mantidos a cada geração
+    pub tamanho_populacao: usize,
+    /// Configuração do salvamento periódico do estado
+    salvamento: Option<Salvamento<Gene>>,
+    /// Informação se o observador já foi notificado do início da busca
+    iniciado: bool,
+    /// Informação se o objetivo já foi satisfeito
+    concluido: bool,
+    /// Tempo gasto executando as gerações
+    tempo: Duration,
+    /// Estatísticas de cada geração, quando habilitadas
+    historico: Option<Vec<EstatisticaGeracao>>,
+}
+
+/// Salvamento periódico do estado do AG. A função de salvar é guardada aqui para que apenas
+/// quem usa o salvamento precise de genes serializáveis.
+struct Salvamento<Gene> {
+    intervalo: usize,
+    caminho: PathBuf,
+    salvar: fn(&Populacao<Gene>, usize, Value, &Aleatorio, &Path) -> io::Result<()>,
+}
+
+fn salvar_estado<Gene>(pop: &Populacao<Gene>,
+                       geracao: usize,
+                       objetivo: Value,
+                       aleatorio: &Aleatorio,
+                       caminho: &Path)
+                       -> io::Result<()>
+    where Gene: Clone + Serialize
+{
+    EstadoGenetico {
+            geracao,
+            individuos: pop.individuos.clone(),
+            objetivo,
+            aleatorio: aleatorio.clone(),
+            avaliacoes: pop.avaliacoes(),
+        }
+        .salvar(caminho)
 }
 
 /// Deve ler: Uma implementação que serve pera qualquer tipo de Gene/Aptidao/Objetivo/Evolução
@@ -30,16 +84,100 @@ impl<Gene, Evol, Obj> Genetico<Gene, Evol, Obj>
           Obj: Objetivo<Gene>
 {
     /// Cria uma nova instância do algoritmo genético
+    #[allow(clippy::redundant_field_names)]
     pub fn criar(pop: Populacao<Gene>, evolucao: Evol, objetivo: Obj) -> Self {
         Genetico {
+            tamanho_populacao: pop.individuos.len(),
             populacao: pop,
             evolucao: evolucao,
             objetivo: objetivo,
             observador: Box::new(ObservadorEvolucaoVazio),
             elitismo: false,
+            aleatorio: Aleatorio::criar(),
+            geracao: 0,
+            salvamento: None,
+            iniciado: false,
+            concluido: false,
+            tempo: Duration::from_secs(0),
+            historico: None,
         }
     }
 
+    /// Cria o AG com a população gerada por *criar_populacao* usando o gerador *aleatorio*, que
+    /// depois é o mesmo gerador usado nas evoluções. Com um gerador criado a partir de uma
+    /// semente toda a execução, da população inicial à última geração, pode ser reproduzida.
+    pub fn criar_com_aleatorio<F>(mut aleatorio: Aleatorio,
+                                  criar_populacao: F,
+                                  evolucao: Evol,
+                                  objetivo: Obj)
+                                  -> Self
+        where F: FnOnce(&mut Aleatorio) -> Populacao<Gene>
+    {
+        let pop = criar_populacao(&mut aleatorio);
+        let mut genetico = Genetico::criar(pop, evolucao, objetivo);
+        genetico.aleatorio = aleatorio;
+        genetico
+    }
+
+    /// Salva o estado do AG no arquivo *caminho* a cada *intervalo* gerações. O arquivo é
+    /// sobrescrito a cada salvamento.
+    pub fn salvar_a_cada<P>(&mut self, intervalo: usize, caminho: P)
+        where P: AsRef<Path>,
+              Gene: Clone + Serialize
+    {
+        self.salvamento = Some(Salvamento {
+            intervalo,
+            caminho: caminho.as_ref().to_path_buf(),
+            salvar: salvar_estado::<Gene>,
+        });
+    }
+
+    /// O estado atual do AG.
+    pub fn estado(&self) -> EstadoGenetico<Gene>
+        where Gene: Clone
+    {
+        EstadoGenetico {
+            geracao: self.geracao,
+            individuos: self.populacao.individuos.clone(),
+            objetivo: self.objetivo.estado(),
+            aleatorio: self.aleatorio.clone(),
+            avaliacoes: self.populacao.avaliacoes(),
+        }
+    }
+
+    /// Restaura o estado salvo em *caminho*, de forma que `buscar_solucao` continue a execução
+    /// do ponto em que ela foi salva. A população, a evolução e o objetivo devem ser
+    /// configurados da mesma forma que na execução original.
+    pub fn restaurar<P>(&mut self, caminho: P) -> io::Result<()>
+        where P: AsRef<Path>,
+              Gene: DeserializeOwned
+    {
+        let estado = EstadoGenetico::carregar(caminho)?;
+        info!("Restaurando execução da geração {}", estado.geracao);
+
+        self.tamanho_populacao = estado.individuos.len();
+        self.concluido = false;
+        self.populacao.individuos = estado.individuos;
+        self.populacao.definir_avaliacoes(estado.avaliacoes);
+        self.geracao = estado.geracao;
+        self.objetivo.restaurar(&estado.objetivo);
+        self.aleatorio = estado.aleatorio;
+        Ok(())
+    }
+
+    /// Define a semente do gerador de números aleatórios usado nas evoluções. A população já
+    /// criada não muda, para reproduzir toda a execução a partir de uma única semente use
+    /// `Genetico::criar_com_aleatorio`.
+    pub fn semente(&mut self, semente: u64) {
+        self.aleatorio = Aleatorio::semente(semente);
+    }
+
+    /// Registra as estatísticas da população em cada geração, elas são devolvidas no histórico
+    /// do resultado.
+    pub fn registrar_historico(&mut self) {
+        self.historico = Some(Vec::new());
+    }
+
     /// Define um Observador para a evolução do algoritmo.
     pub fn observador<T>(&mut self, observador: T)
         where T: ObservadorEvolucao<Gene> + 'static
@@ -47,46 +185,120 @@ impl<Gene, Evol, Obj> Genetico<Gene, Evol, Obj>
         self.observador = Box::new(observador);
     }
 
-    /// Busca pela solução para o algoritmo genético
-    pub fn buscar_solucao(mut self) -> Gene {
+    /// Executa uma única geração do AG. Retorna o número da geração executada ou None quando
+    /// o objetivo foi satisfeito pelo indivíduo mais apto da população atual.
+    ///
+    /// Entre as chamadas a população e os parâmetros podem ser consultados e alterados
+    /// livremente, por exemplo adicionando indivíduos ou trocando o elitismo.
+    pub fn proxima_geracao(&mut self) -> Option<usize> {
 
-        let mut pop = self.populacao;
-        let observador = self.observador;
-        let populacao_inicial = pop.individuos.len();
+        let inicio = Instant::now();
 
         // Notificando o observador que vamos iniciar a busca pela solução
-        observador.inicio(&pop);
+        if !self.iniciado {
+            self.iniciado = true;
+            self.observador.inicio(&self.populacao);
+            if let Some(ref mut historico) = self.historico {
+                historico.push(EstatisticaGeracao::calcular(self.geracao, &self.populacao));
+            }
+        }
 
-        let mut ger = 0;
-        let mut mais_apto = pop.remover_mais_apto();
+        if self.concluido ||
+           self.objetivo.satisfeito_por(&Situacao::criar(self.geracao, &self.populacao)) {
+            self.concluido = true;
+            self.tempo += inicio.elapsed();
+            return None;
+        }
 
-        // Executando até que o objetivo seja satisfeito
-        while !self.objetivo.satisfeito_por(&mais_apto) {
+        // Evoluindo a população
+        self.populacao.definir_geracao(self.geracao);
+        self.geracao += 1;
+        let ger = self.geracao;
+
+        let pop = self.populacao.preparar_nova_geracao();
+        let pop = mem::replace(&mut self.populacao, pop);
+        self.populacao = evoluir_geracao(pop,
+                                         &self.evolucao,
+                                         self.elitismo,
+                                         self.tamanho_populacao,
+                                         &mut self.aleatorio);
+        self.populacao.definir_geracao(ger);
+
+        // Informando ao observador sobre o avançar de uma geração
+        self.observador.geracao(ger, &self.populacao);
+        if let Some(ref mut historico) = self.historico {
+            historico.push(EstatisticaGeracao::calcular(ger, &self.populacao));
+        }
 
-            ger = ger + 1;
-            pop.reiterar(mais_apto);
+        if let Some(ref salvamento) = self.salvamento {
+            if salvamento.intervalo > 0 && ger % salvamento.intervalo == 0 {
+                debug!("Salvando a geração {}", ger);
+                if let Err(erro) = (salvamento.salvar)(&self.populacao,
+                                                       ger,
+                                                       self.objetivo.estado(),
+                                                       &self.aleatorio,
+                                                       &salvamento.caminho) {
+                    error!("Falha ao salvar a geração {}: {}", ger, erro);
+                }
+            }
+        }
+
+        self.tempo += inicio.elapsed();
+        Some(ger)
+    }
 
-            // Evoluindo a população
-            let mut nova_pop = self.evolucao.evoluir(&mut pop);
+    /// Encerra a execução devolvendo o indivíduo mais apto da população atual e as informações
+    /// sobre as gerações executadas. O observador é notificado do fim da execução.
+    pub fn resultado(mut self) -> Resultado<Gene> {
+        let objetivo = if self.concluido {
+            Some(self.objetivo.descricao())
+        } else {
+            None
+        };
+
+        let resultado = Resultado {
+            melhor: self.populacao.remover_mais_apto(),
+            geracoes: self.geracao,
+            avaliacoes: self.populacao.avaliacoes(),
+            objetivo,
+            tempo: self.tempo,
+            historico: self.historico,
+        };
+
+        self.observador.fim(&resultado);
+        resultado
+    }
 
-            // Aplicando elitismo quando informado.
-            if self.elitismo {
-                info!("Aplicando elitismo");
-                nova_pop.reter(pop);
-                pop = nova_pop
-            } else {
-                pop = nova_pop;
-            }
+    /// Busca pela solução para o algoritmo genético
+    pub fn buscar_solucao(mut self) -> Resultado<Gene> {
 
-            pop.manter_mais_aptos(populacao_inicial);
+        // Executando até que o objetivo seja satisfeito
+        while self.proxima_geracao().is_some() {}
 
-            // Informando ao observador sobre o avançar de uma geração
-            observador.geracao(ger, &pop);
-            mais_apto = pop.remover_mais_apto();
-        }
+        self.resultado()
+    }
+}
+
+/// Executa uma geração do AG: evolui a população, aplica o elitismo quando informado e mantém
+/// apenas os *tamanho* indivíduos mais aptos.
+pub fn evoluir_geracao<Gene, Evol>(mut pop: Populacao<Gene>,
+                                   evolucao: &Evol,
+                                   elitismo: bool,
+                                   tamanho: usize,
+                                   aleatorio: &mut Aleatorio)
+                                   -> Populacao<Gene>
+    where Evol: Evolucao<Gene> + ?Sized
+{
+    let mut nova_pop = evolucao.evoluir(&mut pop, aleatorio);
 
-        mais_apto.genes
+    // Aplicando elitismo quando informado.
+    if elitismo {
+        info!("Aplicando elitismo");
+        nova_pop.reter(pop);
     }
+
+    nova_pop.manter_mais_aptos(tamanho);
+    nova_pop
 }
 
 
@@ -94,10 +306,171 @@ impl<Gene, Evol, Obj> Genetico<Gene, Evol, Obj>
 mod test {
 
     use super::*;
-    use populacao::*;
-    use evolucao::*;
-    use objetivo::*;
     use aptidao::*;
+    use evolucao::cruzamento::*;
+    use evolucao::mutacao::*;
+    use evolucao::selecao::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn mesma_semente_deve_achar_mesma_solucao() {
+        assert_eq!(executa_com_semente(7), executa_com_semente(7));
+    }
+
+    #[test]
+    fn deve_continuar_execucao_salva() {
+        use std::env;
+
+        let caminho = env::temp_dir().join("genetics_deve_continuar_execucao_salva.json");
+
+        // Executa 10 gerações salvando o estado no final
+        let mut genetico = cria_genetico(5, 10);
+        genetico.salvar_a_cada(5, &caminho);
+        genetico.buscar_solucao();
+
+        // O arquivo temporário da gravação é renomeado para o arquivo final
+        assert!(caminho.exists());
+        assert!(!caminho.with_extension("json.tmp").exists());
+
+        // Continua a execução salva até a geração 30
+        let mut continuacao = cria_genetico(99, 30);
+        continuacao.restaurar(&caminho).unwrap();
+        assert_eq!(10, continuacao.geracao);
+
+        let original = cria_genetico(5, 30).buscar_solucao();
+        let continuado = continuacao.buscar_solucao();
+        assert_eq!(original.melhor.genes, continuado.melhor.genes);
+        assert_eq!(original.avaliacoes, continuado.avaliacoes);
+    }
+
+    #[test]
+    fn deve_executar_geracao_a_geracao() {
+        let mut genetico = cria_genetico(3, 5);
+
+        assert_eq!(Some(1), genetico.proxima_geracao());
+        assert_eq!(Some(2), genetico.proxima_geracao());
+
+        // Injetando um indivíduo entre as gerações e mantendo ele com elitismo
+        genetico.populacao.adicionar(vec![9; 8]);
+        genetico.elitismo = true;
+
+        while genetico.proxima_geracao().is_some() {}
+        assert_eq!(5, genetico.geracao);
+        assert_eq!(None, genetico.proxima_geracao());
+        assert_eq!(72.0, genetico.populacao.melhor_aptidao());
+    }
+
+    #[test]
+    fn deve_informar_resultado_da_execucao() {
+        let mut genetico = cria_genetico(1, 10);
+        genetico.registrar_historico();
+        let resultado = genetico.buscar_solucao();
+
+        assert_eq!(10, resultado.geracoes);
+        assert_eq!(Some("Número máximo de iterações (10)".to_string()), resultado.objetivo);
+        assert!(resultado.avaliacoes > 20);
+
+        let historico = resultado.historico.unwrap();
+        assert_eq!(11, historico.len());
+        assert_eq!(0, historico[0].geracao);
+        assert_eq!(20, historico[0].avaliacoes);
+        assert_eq!(resultado.avaliacoes, historico[10].avaliacoes);
+        assert_eq!(resultado.melhor.aptidao, historico[10].melhor);
+    }
+
+    #[test]
+    fn resultado_interrompido_nao_tem_objetivo() {
+        let mut genetico = cria_genetico(1, 10);
+        genetico.proxima_geracao();
+        let resultado = genetico.resultado();
+
+        assert_eq!(1, resultado.geracoes);
+        assert_eq!(None, resultado.objetivo);
+        assert!(resultado.historico.is_none());
+    }
+
+    /// Observador que registra os eventos recebidos.
+    struct ObservadorEventos {
+        eventos: Rc<RefCell<Vec<String>>>,
+        geracoes: usize,
+    }
+
+    impl ObservadorEvolucao<Vec<usize>> for ObservadorEventos {
+        fn inicio(&mut self, _: &Populacao<Vec<usize>>) {
+            self.eventos.borrow_mut().push("inicio".to_string());
+        }
+
+        fn geracao(&mut self, _: usize, _: &Populacao<Vec<usize>>) {
+            self.geracoes += 1;
+        }
+
+        fn fim(&mut self, resultado: &Resultado<Vec<usize>>) {
+            self.eventos
+                .borrow_mut()
+                .push(format!("fim {} {}", self.geracoes, resultado.geracoes));
+        }
+    }
+
+    #[test]
+    fn deve_notificar_todos_observadores() {
+        let eventos = Rc::new(RefCell::new(Vec::new()));
+
+        let mut observador = ObservadorMisto::criar();
+        for _ in 0..2 {
+            observador.adicionar(ObservadorEventos {
+                eventos: eventos.clone(),
+                geracoes: 0,
+            });
+        }
+
+        let mut genetico = cria_genetico(2, 4);
+        genetico.observador(observador);
+        genetico.buscar_solucao();
+
+        assert_eq!(vec!["inicio", "inicio", "fim 4 4", "fim 4 4"], *eventos.borrow());
+    }
+
+    fn executa_com_semente(semente: u64) -> Vec<usize> {
+        cria_genetico(semente, 30).buscar_solucao().melhor.genes
+    }
+
+    fn cria_genetico(semente: u64,
+                     geracoes: usize)
+                     -> Genetico<Vec<usize>, EvolucaoMista<Vec<usize>>, NumeroMaximoIteracoes> {
+
+        let mut evolucao = EvolucaoMista::criar();
+        evolucao.adicionar(Mutacao::criar(0.2, MutagenicoVetor { estados: vec![10; 8] }));
+        let torneio = SelecaoPorTorneio::criar(3).unwrap();
+        evolucao.adicionar(Cruzamento::criar(torneio, CruzadorVetor, 0.9));
+
+        Genetico::criar_com_aleatorio(Aleatorio::semente(semente),
+                                      |aleatorio| {
+                                          Populacao::criar_com_aleatorio(AptidaoSoma,
+                                                                         &CriadorVetor,
+                                                                         20,
+                                                                         Operacao::Max,
+                                                                         aleatorio)
+                                      },
+                                      evolucao,
+                                      NumeroMaximoIteracoes::criar(geracoes))
+    }
+
+    struct AptidaoSoma;
+
+    impl Aptidao<Vec<usize>> for AptidaoSoma {
+        fn calcular_aptidao(&self, genes: &Vec<usize>) -> f64 {
+            genes.iter().sum::<usize>() as f64
+        }
+    }
+
+    struct CriadorVetor;
+
+    impl CriadorIndividuos<Vec<usize>> for CriadorVetor {
+        fn criar(&self, aleatorio: &mut Aleatorio) -> Vec<usize> {
+            (0..8).map(|_| aleatorio.intervalo(0, 10)).collect()
+        }
+    }
 
     #[test]
     fn deve_achar_objetivo() {
@@ -112,7 +485,9 @@ mod test {
         let genetico = Genetico::criar(pop, EvolucaoSimples, ObjetivoSimples { valor: 6 });
 
         let resultado = genetico.buscar_solucao();
-        assert_eq!(6, resultado);
+        assert_eq!(6, resultado.melhor.genes);
+        assert_eq!(6.0, resultado.melhor.aptidao);
+        assert_eq!(2, resultado.geracoes);
 
 
     }
@@ -122,8 +497,8 @@ mod test {
     }
 
     impl Objetivo<usize> for ObjetivoSimples {
-        fn satisfeito_por(&mut self, ind: &Individuo<usize>) -> bool {
-            self.valor == ind.genes
+        fn satisfeito_por(&mut self, situacao: &Situacao<usize>) -> bool {
+            self.valor == situacao.mais_apto.genes
         }
     }
 
@@ -131,7 +506,7 @@ mod test {
     struct EvolucaoSimples;
 
     impl Evolucao<usize> for EvolucaoSimples {
-        fn evoluir(&self, pop: &mut Populacao<usize>) -> Populacao<usize> {
+        fn evoluir(&self, pop: &mut Populacao<usize>, _: &mut Aleatorio) -> Populacao<usize> {
 
             let mut nova_pop = pop.preparar_nova_geracao();
             for ind in pop.individuos.iter() {
@@ -0,0 +1,398 @@
+//! # Modelo de ilhas
+//!
+//! Evolui várias populações independentes, cada uma em uma thread, trocando indivíduos entre
+//! elas de tempos em tempos. Populações separadas demoram mais para convergir, o que ajuda em
+//! funções com muitos ótimos locais.
+
+use crossbeam;
+use std::cmp::Ordering;
+use std::mem;
+use std::time::Instant;
+
+use aleatorio::Aleatorio;
+use evolucao::Evolucao;
+use genetico::evoluir_geracao;
+use objetivo::{Objetivo, Situacao};
+use populacao::*;
+use resultado::{EstatisticaGeracao, Resultado};
+
+/// Define para quais ilhas os indivíduos de uma ilha migram.
+#[derive(Clone, Debug)]
+pub enum Topologia {
+    /// Cada ilha envia indivíduos para a ilha seguinte, a última envia para a primeira.
+    Anel,
+    /// Cada ilha envia indivíduos para todas as outras.
+    Completa,
+    /// Cada ilha envia indivíduos para uma outra ilha sorteada a cada migração.
+    Aleatoria,
+}
+
+/// Define quais indivíduos deixam a ilha em uma migração.
+#[derive(Clone, Debug)]
+pub enum Migracao {
+    /// Os mais aptos da ilha
+    Melhores,
+    /// Indivíduos sorteados
+    Aleatorios,
+}
+
+/// Uma das populações do modelo e a evolução aplicada a ela.
+pub struct Ilha<Gene> {
+    pub populacao: Populacao<Gene>,
+    pub evolucao: Box<dyn Evolucao<Gene> + Send + Sync>,
+}
+
+/// Algoritmo genético com várias populações isoladas que trocam indivíduos periodicamente.
+pub struct ModeloIlhas<Gene, Obj>
+    where Obj: Objetivo<Gene>
+{
+    /// As ilhas do modelo
+    pub ilhas: Vec<Ilha<Gene>>,
+    /// O objetivo que se deseja atingir, avaliado a cada geração com o melhor indivíduo de
+    /// todas as ilhas
+    pub objetivo: Obj,
+    /// Para onde os indivíduos migram
+    pub topologia: Topologia,
+    /// Quais indivíduos migram
+    pub migracao: Migracao,
+    /// Quantidade de gerações entre as migrações, 0 desliga a migração
+    pub intervalo_migracao: usize,
+    /// Quantidade de indivíduos enviados por cada ilha a cada destino
+    pub quantidade_migrantes: usize,
+    /// Informação se deve ou não ser utilizado elitismo entre as gerações.
+    pub elitismo: bool,
+    /// Gerador de números aleatórios. Os geradores de cada ilha são derivados dele.
+    pub aleatorio: Aleatorio,
+    /// Estatísticas de todas as ilhas em cada geração, quando habilitadas
+    historico: Option<Vec<EstatisticaGeracao>>,
+}
+
+impl<Gene, Obj> ModeloIlhas<Gene, Obj>
+    where Obj: Objetivo<Gene>,
+          Gene: Clone + Send + Sync
+{
+    /// Cria um modelo sem ilhas, com migração em anel do melhor indivíduo a cada 10 gerações.
+    pub fn criar(objetivo: Obj) -> Self {
+        ModeloIlhas {
+            ilhas: Vec::new(),
+            objetivo,
+            topologia: Topologia::Anel,
+            migracao: Migracao::Melhores,
+            intervalo_migracao: 10,
+            quantidade_migrantes: 1,
+            elitismo: false,
+            aleatorio: Aleatorio::criar(),
+            historico: None,
+        }
+    }
+
+    /// Adiciona uma ilha com a sua própria evolução. Para aplicar várias evoluções na mesma
+    /// ilha use a evolução mista criada com `EvolucaoMista::criar_para_ilhas`.
+    pub fn adicionar<Evol>(&mut self, populacao: Populacao<Gene>, evolucao: Evol)
+        where Evol: Evolucao<Gene> + Send + Sync + 'static
+    {
+        self.ilhas.push(Ilha {
+            populacao,
+            evolucao: Box::new(evolucao),
+        });
+    }
+
+    /// Define a semente do gerador de números aleatórios do modelo.
+    pub fn semente(&mut self, semente: u64) {
+        self.aleatorio = Aleatorio::semente(semente);
+    }
+
+    /// Registra as estatísticas de todas as ilhas em cada geração, elas são devolvidas no
+    /// histórico do resultado.
+    pub fn registrar_historico(&mut self) {
+        self.historico = Some(Vec::new());
+    }
+
+    /// Busca pela solução evoluindo todas as ilhas e devolve o melhor indivíduo encontrado
+    /// entre todas elas, com as informações sobre as gerações executadas.
+    pub fn buscar_solucao(mut self) -> Resultado<Gene> {
+
+        let inicio = Instant::now();
+        let mut aleatorio = self.aleatorio.clone();
+        let mut aleatorios: Vec<Aleatorio> = self.ilhas
+                                                 .iter()
+                                                 .map(|_| aleatorio.derivar())
+                                                 .collect();
+        let tamanhos: Vec<usize> = self.ilhas
+                                       .iter()
+                                       .map(|i| i.populacao.individuos.len())
+                                       .collect();
+        let elitismo = self.elitismo;
+
+        let mut ger = 0;
+        self.registrar(ger);
+        while !self.satisfeito(ger) {
+            ger += 1;
+
+            // Evoluindo cada ilha na sua própria thread
+            crossbeam::scope(|escopo| {
+                let ilhas = self.ilhas.iter_mut().zip(aleatorios.iter_mut()).zip(tamanhos.iter());
+                for ((ilha, aleatorio_ilha), tamanho) in ilhas {
+                    escopo.spawn(move |_| {
+                        ilha.populacao.definir_geracao(ger - 1);
+                        let pop = ilha.populacao.preparar_nova_geracao();
+                        let pop = mem::replace(&mut ilha.populacao, pop);
+                        ilha.populacao = evoluir_geracao(pop,
+                                                         &*ilha.evolucao,
+                                                         elitismo,
+                                                         *tamanho,
+                                                         aleatorio_ilha);
+                        ilha.populacao.definir_geracao(ger);
+                    });
+                }
+            })
+                .expect("Falha na evolução das ilhas");
+
+            if self.intervalo_migracao > 0 && ger % self.intervalo_migracao == 0 {
+                debug!("Migrando indivíduos na geração {}", ger);
+                self.migrar(&mut aleatorio);
+            }
+            self.registrar(ger);
+        }
+
+        Resultado {
+            melhor: self.mais_apto(),
+            geracoes: ger,
+            avaliacoes: self.ilhas.iter().map(|ilha| ilha.populacao.avaliacoes()).sum(),
+            objetivo: Some(self.objetivo.descricao()),
+            tempo: inicio.elapsed(),
+            historico: self.historico,
+        }
+    }
+
+    /// Guarda no histórico, quando habilitado, as estatísticas de todas as ilhas na *geracao*.
+    fn registrar(&mut self, geracao: usize) {
+        if self.historico.is_none() {
+            return;
+        }
+
+        let mais_apto = self.mais_apto();
+        let situacao = Situacao {
+            geracao,
+            mais_apto: &mais_apto,
+            populacoes: self.ilhas.iter().map(|ilha| &ilha.populacao).collect(),
+        };
+        let estatistica = EstatisticaGeracao::calcular_situacao(&situacao);
+        if let Some(ref mut historico) = self.historico {
+            historico.push(estatistica);
+        }
+    }
+
+    /// Avalia o objetivo com o mais apto e as populações de todas as ilhas.
+    fn satisfeito(&mut self, geracao: usize) -> bool {
+        let mais_apto = self.mais_apto();
+        let situacao = Situacao {
+            geracao,
+            mais_apto: &mais_apto,
+            populacoes: self.ilhas.iter().map(|ilha| &ilha.populacao).collect(),
+        };
+        self.objetivo.satisfeito_por(&situacao)
+    }
+
+    /// O indivíduo mais apto entre todas as ilhas.
+    fn mais_apto(&self) -> Individuo<Gene> {
+        let mut mais_apto: Option<&Individuo<Gene>> = None;
+        for ilha in self.ilhas.iter() {
+            let operacao = ilha.populacao.operacao();
+            for ind in ilha.populacao.individuos.iter() {
+                let melhor = match mais_apto {
+                    Some(atual) => {
+                        operacao.compara(&ind.aptidao, &atual.aptidao) == Ordering::Greater
+                    }
+                    None => true,
+                };
+                if melhor {
+                    mais_apto = Some(ind);
+                }
+            }
+        }
+
+        mais_apto.expect("Modelo de ilhas sem indivíduos").clone()
+    }
+
+    /// Envia os migrantes de cada ilha para os destinos da topologia. Os migrantes substituem os
+    /// indivíduos menos aptos dos destinos.
+    pub fn migrar(&mut self, aleatorio: &mut Aleatorio) {
+
+        let qtd_ilhas = self.ilhas.len();
+        if qtd_ilhas < 2 {
+            return;
+        }
+
+        // Escolhendo todos os migrantes antes de mexer nas populações
+        let mut chegadas: Vec<Vec<Individuo<Gene>>> = vec![Vec::new(); qtd_ilhas];
+        for origem in 0..qtd_ilhas {
+            let migrantes = self.escolher_migrantes(origem, aleatorio);
+            for destino in self.destinos(origem, aleatorio) {
+                chegadas[destino].extend(migrantes.iter().cloned());
+            }
+        }
+
+        for (ilha, migrantes) in self.ilhas.iter_mut().zip(chegadas) {
+            let tamanho = ilha.populacao.individuos.len();
+            let manter = tamanho.saturating_sub(migrantes.len());
+            ilha.populacao.manter_mais_aptos(manter);
+            for migrante in migrantes.into_iter().take(tamanho) {
+                ilha.populacao.reiterar(migrante);
+            }
+        }
+    }
+
+    fn escolher_migrantes(&mut self,
+                          origem: usize,
+                          aleatorio: &mut Aleatorio)
+                          -> Vec<Individuo<Gene>> {
+        let pop = &mut self.ilhas[origem].populacao;
+        let qtd = self.quantidade_migrantes.min(pop.individuos.len());
+
+        match self.migracao {
+            Migracao::Melhores => {
+                pop.manter_mais_aptos(pop.individuos.len());
+                pop.individuos[..qtd].to_vec()
+            }
+            Migracao::Aleatorios => {
+                (0..qtd)
+                    .map(|_| pop.individuos[aleatorio.intervalo(0, pop.individuos.len())].clone())
+                    .collect()
+            }
+        }
+    }
+
+    fn destinos(&self, origem: usize, aleatorio: &mut Aleatorio) -> Vec<usize> {
+        let qtd_ilhas = self.ilhas.len();
+        match self.topologia {
+            Topologia::Anel => vec![(origem + 1) % qtd_ilhas],
+            Topologia::Completa => (0..qtd_ilhas).filter(|d| *d != origem).collect(),
+            Topologia::Aleatoria => {
+                let sorteio = aleatorio.intervalo(0, qtd_ilhas - 1);
+                if sorteio < origem {
+                    vec![sorteio]
+                } else {
+                    vec![sorteio + 1]
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use aptidao::*;
+    use evolucao::EvolucaoMista;
+    use objetivo::*;
+
+    /// Evolução que não altera a população, útil para isolar a migração.
+    struct EvolucaoParada;
+
+    impl Evolucao<usize> for EvolucaoParada {
+        fn evoluir(&self, pop: &mut Populacao<usize>, _: &mut Aleatorio) -> Populacao<usize> {
+            let mut nova_pop = pop.preparar_nova_geracao();
+            for ind in pop.individuos.iter() {
+                nova_pop.adicionar(ind.genes);
+            }
+            nova_pop
+        }
+    }
+
+    /// Evolução que soma um valor sorteado em cada gene.
+    struct EvolucaoSorteio;
+
+    impl Evolucao<usize> for EvolucaoSorteio {
+        fn evoluir(&self,
+                   pop: &mut Populacao<usize>,
+                   aleatorio: &mut Aleatorio)
+                   -> Populacao<usize> {
+            let mut nova_pop = pop.preparar_nova_geracao();
+            for ind in pop.individuos.iter() {
+                nova_pop.adicionar(ind.genes + aleatorio.intervalo(0, 10));
+            }
+            nova_pop
+        }
+    }
+
+    fn modelo(topologia: Topologia) -> ModeloIlhas<usize, NumeroMaximoIteracoes> {
+        let mut modelo = ModeloIlhas::criar(NumeroMaximoIteracoes::criar(0));
+        modelo.topologia = topologia;
+        modelo.adicionar(cria_populacao(&vec![1, 2, 3], Operacao::Max), EvolucaoParada);
+        modelo.adicionar(cria_populacao(&vec![10, 20, 30], Operacao::Max), EvolucaoParada);
+        modelo.adicionar(cria_populacao(&vec![100, 200, 300], Operacao::Max), EvolucaoParada);
+        modelo
+    }
+
+    fn genes(ilha: &Ilha<usize>) -> Vec<usize> {
+        let mut genes: Vec<usize> = ilha.populacao.individuos.iter().map(|i| i.genes).collect();
+        genes.sort();
+        genes
+    }
+
+    #[test]
+    fn deve_migrar_em_anel() {
+        let mut modelo = modelo(Topologia::Anel);
+        modelo.migrar(&mut Aleatorio::semente(0));
+
+        assert_eq!(genes(&modelo.ilhas[0]), vec![2, 3, 300]);
+        assert_eq!(genes(&modelo.ilhas[1]), vec![3, 20, 30]);
+        assert_eq!(genes(&modelo.ilhas[2]), vec![30, 200, 300]);
+    }
+
+    #[test]
+    fn deve_migrar_para_todas_ilhas() {
+        let mut modelo = modelo(Topologia::Completa);
+        modelo.migrar(&mut Aleatorio::semente(0));
+
+        assert_eq!(genes(&modelo.ilhas[0]), vec![3, 30, 300]);
+        assert_eq!(genes(&modelo.ilhas[1]), vec![3, 30, 300]);
+        assert_eq!(genes(&modelo.ilhas[2]), vec![3, 30, 300]);
+    }
+
+    #[test]
+    fn mesma_semente_deve_achar_mesma_solucao() {
+        let executa = |semente| {
+            let mut modelo = ModeloIlhas::criar(NumeroMaximoIteracoes::criar(20));
+            modelo.topologia = Topologia::Aleatoria;
+            modelo.migracao = Migracao::Aleatorios;
+            modelo.intervalo_migracao = 3;
+            for _ in 0..4 {
+                let mut evolucao = EvolucaoMista::criar_para_ilhas();
+                evolucao.adicionar(EvolucaoSorteio);
+                modelo.adicionar(Populacao::criar_vazia(AptidaoSimples, Operacao::Max), evolucao);
+            }
+            for ilha in modelo.ilhas.iter_mut() {
+                ilha.populacao.adicionar_lote(vec![1, 2, 3, 4, 5]);
+            }
+            modelo.semente(semente);
+            modelo.buscar_solucao().melhor.genes
+        };
+
+        assert_eq!(executa(11), executa(11));
+    }
+
+    #[test]
+    fn deve_informar_o_resultado_de_todas_ilhas() {
+        let mut modelo = modelo(Topologia::Anel);
+        modelo.objetivo = NumeroMaximoIteracoes::criar(4);
+        modelo.registrar_historico();
+        let resultado = modelo.buscar_solucao();
+
+        assert_eq!(300, resultado.melhor.genes);
+        assert_eq!(4, resultado.geracoes);
+        // 9 indivíduos iniciais e mais 9 avaliações por geração
+        assert_eq!(45, resultado.avaliacoes);
+        assert_eq!(Some("Número máximo de iterações (4)".to_string()), resultado.objetivo);
+
+        let historico = resultado.historico.unwrap();
+        assert_eq!(5, historico.len());
+        assert_eq!(0, historico[0].geracao);
+        assert_eq!(9, historico[0].avaliacoes);
+        assert_eq!(1.0, historico[0].pior);
+        assert_eq!(300.0, historico[4].melhor);
+        assert_eq!(resultado.avaliacoes, historico[4].avaliacoes);
+    }
+}
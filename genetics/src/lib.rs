@@ -1,8 +1,13 @@
 //! Biblioteca com rotinas que possibilitam o uso de algoritmos genéticos (AG).
+
 extern crate rand;
 #[macro_use]
 extern crate log;
 extern crate crossbeam;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
 
 /// Interface simplificada para o gerador de números aleatórios.
 pub mod aleatorio;
@@ -18,3 +23,15 @@ pub mod objetivo;
 pub mod aptidao;
 /// Definição o contrato para implementações que desejam observar a evolução do AG.
 pub mod observador;
+/// Otimização com vários objetivos usando fronteiras de Pareto (NSGA-II).
+pub mod multiobjetivo;
+/// Modelo de ilhas: várias populações evoluindo em paralelo com migração.
+pub mod ilhas;
+/// Salvamento do estado do AG para continuar a execução mais tarde.
+pub mod salvamento;
+/// Resultado de uma execução do AG.
+pub mod resultado;
+/// Medidas de diversidade da população.
+pub mod diversidade;
+/// Genoma de cadeia de bits e decodificação de variáveis binárias.
+pub mod binario;
@@ -0,0 +1,272 @@
+//! Estruturas para otimização com vários objetivos ao mesmo tempo. Aqui a aptidão de um
+//! indivíduo é um vetor de valores e a comparação entre indivíduos é feita por dominância de
+//! Pareto, como no NSGA-II.
+
+/// Implementação do NSGA-II
+pub mod nsga2;
+
+pub use self::nsga2::*;
+
+use std::cmp::Ordering;
+use std::f64;
+use std::sync::Arc;
+
+use aleatorio::Aleatorio;
+use populacao::{CriadorIndividuos, Operacao};
+
+/// Função de aptidão que devolve um valor para cada objetivo.
+pub trait AptidaoMultiobjetivo<Gene> {
+    fn calcular_aptidoes(&self, genes: &Gene) -> Vec<f64>;
+}
+
+/// Indivíduo de uma população com vários objetivos.
+#[derive(Clone, Debug)]
+pub struct IndividuoMultiobjetivo<Gene> {
+    pub genes: Gene,
+    /// Valor de cada um dos objetivos
+    pub aptidoes: Vec<f64>,
+    /// Indice da fronteira de Pareto a que o indivíduo pertence, 0 é a fronteira não dominada
+    pub fronteira: usize,
+    /// Distância de aglomeração dentro da fronteira, quanto maior mais isolado é o indivíduo
+    pub aglomeracao: f64,
+}
+
+impl<Gene> IndividuoMultiobjetivo<Gene> {
+    /// Comparação do NSGA-II: primeiro a fronteira e depois a distância de aglomeração.
+    /// Retorna Ordering::Greater quando *self* é melhor que *outro*.
+    pub fn compara_aglomeracao(&self, outro: &Self) -> Ordering {
+        outro.fronteira
+             .cmp(&self.fronteira)
+             .then(self.aglomeracao.partial_cmp(&outro.aglomeracao).unwrap_or(Ordering::Equal))
+    }
+}
+
+/// Evolução de uma população com vários objetivos.
+pub trait EvolucaoMultiobjetivo<Gene> {
+    fn evoluir(&self,
+               pop: &mut PopulacaoMultiobjetivo<Gene>,
+               aleatorio: &mut Aleatorio)
+               -> PopulacaoMultiobjetivo<Gene>;
+}
+
+/// População com vários objetivos. Cada objetivo tem a sua própria operação.
+pub struct PopulacaoMultiobjetivo<Gene> {
+    /// Implementação que calcula os objetivos dos indivíduos
+    pub aptidao: Arc<dyn AptidaoMultiobjetivo<Gene> + Send + Sync>,
+    /// A lista de indivíduos dessa população
+    pub individuos: Vec<IndividuoMultiobjetivo<Gene>>,
+    /// A operação de cada objetivo, na mesma ordem dos valores da aptidão
+    pub operacoes: Vec<Operacao>,
+}
+
+impl<Gene> PopulacaoMultiobjetivo<Gene> {
+    /// Cria uma nova população sem nenhum indivíduo
+    pub fn criar_vazia<Apt>(aptidao: Apt, operacoes: Vec<Operacao>) -> Self
+        where Apt: AptidaoMultiobjetivo<Gene> + Send + Sync + 'static
+    {
+        PopulacaoMultiobjetivo {
+            aptidao: Arc::new(aptidao),
+            individuos: Vec::new(),
+            operacoes,
+        }
+    }
+
+    /// Cria uma população com um conjunto de indivíduos já classificados em fronteiras.
+    pub fn criar<Apt, CIA>(aptidao: Apt,
+                           criador: &CIA,
+                           quantidade: usize,
+                           operacoes: Vec<Operacao>,
+                           aleatorio: &mut Aleatorio)
+                           -> Self
+        where Apt: AptidaoMultiobjetivo<Gene> + Send + Sync + 'static,
+              CIA: CriadorIndividuos<Gene>
+    {
+        let mut pop = PopulacaoMultiobjetivo::criar_vazia(aptidao, operacoes);
+        for _ in 0..quantidade {
+            let genes = criador.criar(aleatorio);
+            pop.adicionar(genes);
+        }
+        pop.classificar();
+        pop
+    }
+
+    pub fn adicionar(&mut self, genes: Gene) {
+        let aptidoes = self.aptidao.calcular_aptidoes(&genes);
+        self.individuos.push(IndividuoMultiobjetivo {
+            genes,
+            aptidoes,
+            fronteira: 0,
+            aglomeracao: 0.0,
+        });
+    }
+
+    pub fn preparar_nova_geracao(&self) -> Self {
+        PopulacaoMultiobjetivo {
+            aptidao: self.aptidao.clone(),
+            individuos: Vec::new(),
+            operacoes: self.operacoes.clone(),
+        }
+    }
+
+    /// Calcula a fronteira e a distância de aglomeração de todos os indivíduos.
+    pub fn classificar(&mut self) {
+        let fronteiras = ordenacao_nao_dominada(&self.individuos, &self.operacoes);
+        for (idx_fronteira, fronteira) in fronteiras.iter().enumerate() {
+            let distancias = distancia_aglomeracao(&self.individuos, fronteira);
+            for (idx, distancia) in fronteira.iter().zip(distancias) {
+                self.individuos[*idx].fronteira = idx_fronteira;
+                self.individuos[*idx].aglomeracao = distancia;
+            }
+        }
+    }
+
+    /// Consome a população devolvendo os indivíduos não dominados.
+    pub fn fronteira_pareto(mut self) -> Vec<IndividuoMultiobjetivo<Gene>> {
+        self.classificar();
+        self.individuos.into_iter().filter(|ind| ind.fronteira == 0).collect()
+    }
+}
+
+/// Retorna true quando *a* domina *b*: *a* não é pior em nenhum objetivo e é melhor em pelo
+/// menos um.
+pub fn domina(a: &[f64], b: &[f64], operacoes: &[Operacao]) -> bool {
+    let mut melhor_em_algum = false;
+    for ((va, vb), operacao) in a.iter().zip(b.iter()).zip(operacoes.iter()) {
+        match operacao.compara(va, vb) {
+            Ordering::Less => return false,
+            Ordering::Greater => melhor_em_algum = true,
+            Ordering::Equal => {}
+        }
+    }
+    melhor_em_algum
+}
+
+/// Ordenação não dominada do NSGA-II. Retorna os indices dos indivíduos de cada fronteira,
+/// começando pela fronteira não dominada.
+pub fn ordenacao_nao_dominada<Gene>(individuos: &[IndividuoMultiobjetivo<Gene>],
+                                    operacoes: &[Operacao])
+                                    -> Vec<Vec<usize>> {
+
+    let qtd = individuos.len();
+    let mut dominados = vec![Vec::new(); qtd];
+    let mut qtd_dominantes = vec![0usize; qtd];
+    let mut fronteiras = Vec::new();
+
+    let mut atual = Vec::new();
+    for p in 0..qtd {
+        for q in 0..qtd {
+            if domina(&individuos[p].aptidoes, &individuos[q].aptidoes, operacoes) {
+                dominados[p].push(q);
+            } else if domina(&individuos[q].aptidoes, &individuos[p].aptidoes, operacoes) {
+                qtd_dominantes[p] += 1;
+            }
+        }
+        if qtd_dominantes[p] == 0 {
+            atual.push(p);
+        }
+    }
+
+    while !atual.is_empty() {
+        let mut proxima = Vec::new();
+        for p in atual.iter() {
+            for q in dominados[*p].iter() {
+                qtd_dominantes[*q] -= 1;
+                if qtd_dominantes[*q] == 0 {
+                    proxima.push(*q);
+                }
+            }
+        }
+        fronteiras.push(atual);
+        atual = proxima;
+    }
+
+    fronteiras
+}
+
+/// Distância de aglomeração dos indivíduos de uma fronteira, na mesma ordem de *fronteira*. Os
+/// extremos de cada objetivo recebem distância infinita.
+pub fn distancia_aglomeracao<Gene>(individuos: &[IndividuoMultiobjetivo<Gene>],
+                                   fronteira: &[usize])
+                                   -> Vec<f64> {
+
+    let qtd = fronteira.len();
+    let mut distancias = vec![0.0; qtd];
+    if qtd == 0 {
+        return distancias;
+    }
+
+    let qtd_objetivos = individuos[fronteira[0]].aptidoes.len();
+    for objetivo in 0..qtd_objetivos {
+        let valor = |pos: usize| individuos[fronteira[pos]].aptidoes[objetivo];
+
+        let mut ordem: Vec<usize> = (0..qtd).collect();
+        ordem.sort_by(|a, b| valor(*a).partial_cmp(&valor(*b)).unwrap_or(Ordering::Equal));
+
+        let minimo = valor(ordem[0]);
+        let maximo = valor(ordem[qtd - 1]);
+        distancias[ordem[0]] = f64::INFINITY;
+        distancias[ordem[qtd - 1]] = f64::INFINITY;
+
+        if maximo > minimo {
+            for i in 1..(qtd - 1) {
+                distancias[ordem[i]] += (valor(ordem[i + 1]) - valor(ordem[i - 1])) /
+                                        (maximo - minimo);
+            }
+        }
+    }
+
+    distancias
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn individuo(aptidoes: Vec<f64>) -> IndividuoMultiobjetivo<usize> {
+        IndividuoMultiobjetivo {
+            genes: 0,
+            aptidoes,
+            fronteira: 0,
+            aglomeracao: 0.0,
+        }
+    }
+
+    #[test]
+    fn deve_verificar_dominancia() {
+        let min = [Operacao::Min, Operacao::Min];
+        assert!(domina(&[1.0, 1.0], &[2.0, 2.0], &min));
+        assert!(domina(&[1.0, 2.0], &[2.0, 2.0], &min));
+        assert!(!domina(&[1.0, 3.0], &[2.0, 2.0], &min));
+        assert!(!domina(&[2.0, 2.0], &[2.0, 2.0], &min));
+
+        let misto = [Operacao::Max, Operacao::Min];
+        assert!(domina(&[3.0, 1.0], &[2.0, 2.0], &misto));
+    }
+
+    #[test]
+    fn deve_separar_fronteiras() {
+        let individuos = vec![individuo(vec![1.0, 4.0]),
+                              individuo(vec![2.0, 2.0]),
+                              individuo(vec![3.0, 3.0]),
+                              individuo(vec![4.0, 1.0]),
+                              individuo(vec![5.0, 5.0])];
+
+        let fronteiras = ordenacao_nao_dominada(&individuos, &[Operacao::Min, Operacao::Min]);
+
+        assert_eq!(fronteiras, vec![vec![0, 1, 3], vec![2], vec![4]]);
+    }
+
+    #[test]
+    fn deve_calcular_distancia_aglomeracao() {
+        let individuos = vec![individuo(vec![1.0, 4.0]),
+                              individuo(vec![2.0, 2.0]),
+                              individuo(vec![4.0, 1.0])];
+
+        let distancias = distancia_aglomeracao(&individuos, &[0, 1, 2]);
+
+        assert_eq!(distancias[0], f64::INFINITY);
+        assert_eq!(distancias[2], f64::INFINITY);
+        assert!((distancias[1] - 2.0).abs() < 0.00000001);
+    }
+}
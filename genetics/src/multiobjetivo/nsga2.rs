@@ -0,0 +1,246 @@
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use aleatorio::Aleatorio;
+use evolucao::cruzamento::Cruzador;
+use evolucao::mutacao::Mutagenico;
+use multiobjetivo::*;
+
+/// Evolução do NSGA-II. Os pais são escolhidos por torneio binário usando a fronteira e a
+/// distância de aglomeração, os filhos são gerados por cruzamento e mutação e a próxima geração
+/// é formada pelas melhores fronteiras da união de pais e filhos.
+pub struct Nsga2<Gene, Cruz, Mut>
+    where Cruz: Cruzador<Gene>,
+          Mut: Mutagenico<Gene>
+{
+    /// Implementação que faz o cruzamento dos pais
+    pub cruzador: Cruz,
+    /// Implementação que faz a mutação dos filhos
+    pub mutagenico: Mut,
+    /// Chance de cruzamento de 0 a 1
+    pub chance_cruzamento: f64,
+    /// Chance de mutação de cada filho de 0 a 1
+    pub chance_mutacao: f64,
+
+    phanton_gene: PhantomData<Gene>,
+}
+
+impl<Gene, Cruz, Mut> Nsga2<Gene, Cruz, Mut>
+    where Cruz: Cruzador<Gene>,
+          Mut: Mutagenico<Gene>
+{
+    pub fn criar(cruzador: Cruz,
+                 mutagenico: Mut,
+                 chance_cruzamento: f64,
+                 chance_mutacao: f64)
+                 -> Self {
+        Nsga2 {
+            cruzador,
+            mutagenico,
+            chance_cruzamento,
+            chance_mutacao,
+            phanton_gene: PhantomData,
+        }
+    }
+}
+
+/// Torneio binário usando a comparação por aglomeração.
+fn torneio_binario<Gene>(individuos: &[IndividuoMultiobjetivo<Gene>],
+                         aleatorio: &mut Aleatorio)
+                         -> usize {
+    let a = aleatorio.intervalo(0, individuos.len());
+    let b = aleatorio.intervalo(0, individuos.len());
+
+    if individuos[b].compara_aglomeracao(&individuos[a]) == Ordering::Greater {
+        b
+    } else {
+        a
+    }
+}
+
+impl<Gene, Cruz, Mut> EvolucaoMultiobjetivo<Gene> for Nsga2<Gene, Cruz, Mut>
+    where Cruz: Cruzador<Gene>,
+          Mut: Mutagenico<Gene>,
+          Gene: Clone
+{
+    fn evoluir(&self,
+               pop: &mut PopulacaoMultiobjetivo<Gene>,
+               aleatorio: &mut Aleatorio)
+               -> PopulacaoMultiobjetivo<Gene> {
+
+        debug!("Evoluindo por NSGA-II");
+        let qtd = pop.individuos.len();
+        let mut uniao = pop.preparar_nova_geracao();
+        if qtd == 0 {
+            return uniao;
+        }
+
+        // Gerando os filhos
+        let mut filhos = Vec::with_capacity(qtd);
+        while filhos.len() < qtd {
+            let primeiro = &pop.individuos[torneio_binario(&pop.individuos, aleatorio)].genes;
+            let segundo = &pop.individuos[torneio_binario(&pop.individuos, aleatorio)].genes;
+
+            let (f1, f2) = if self.chance_cruzamento > aleatorio.chance() {
+                self.cruzador.cruzar(aleatorio, primeiro, segundo)
+            } else {
+                (primeiro.clone(), segundo.clone())
+            };
+
+            for filho in [f1, f2] {
+                if filhos.len() < qtd {
+                    if self.chance_mutacao > aleatorio.chance() {
+                        filhos.push(self.mutagenico.mutar(&filho, aleatorio));
+                    } else {
+                        filhos.push(filho);
+                    }
+                }
+            }
+        }
+
+        // Juntando pais e filhos para escolher os melhores
+        uniao.individuos.append(&mut pop.individuos);
+        for filho in filhos {
+            uniao.adicionar(filho);
+        }
+
+        let fronteiras = ordenacao_nao_dominada(&uniao.individuos, &uniao.operacoes);
+        let mut escolhidos = Vec::with_capacity(qtd);
+        for (idx_fronteira, fronteira) in fronteiras.iter().enumerate() {
+            let distancias = distancia_aglomeracao(&uniao.individuos, fronteira);
+            let mut ordem: Vec<(usize, f64)> = fronteira.iter().cloned().zip(distancias).collect();
+
+            if escolhidos.len() + ordem.len() > qtd {
+                // A última fronteira não cabe inteira, ficam os mais isolados
+                ordem.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+                ordem.truncate(qtd - escolhidos.len());
+            }
+
+            for (idx, distancia) in ordem {
+                uniao.individuos[idx].fronteira = idx_fronteira;
+                uniao.individuos[idx].aglomeracao = distancia;
+                escolhidos.push(idx);
+            }
+
+            if escolhidos.len() == qtd {
+                break;
+            }
+        }
+
+        escolhidos.sort();
+        let mut nova_geracao = uniao.preparar_nova_geracao();
+        for (idx, ind) in uniao.individuos.into_iter().enumerate() {
+            if escolhidos.binary_search(&idx).is_ok() {
+                nova_geracao.individuos.push(ind);
+            }
+        }
+
+        nova_geracao
+    }
+}
+
+/// Algoritmo genético com vários objetivos. Executa a evolução por uma quantidade fixa de
+/// gerações e devolve a fronteira de Pareto encontrada.
+pub struct GeneticoMultiobjetivo<Gene, Evol>
+    where Evol: EvolucaoMultiobjetivo<Gene>
+{
+    /// A população que se deseja evoluir
+    pub populacao: PopulacaoMultiobjetivo<Gene>,
+    /// O processo de evolução aplicado a população
+    pub evolucao: Evol,
+    /// Quantidade de gerações executadas
+    pub geracoes: usize,
+    /// Gerador de números aleatórios usado em todas as evoluções
+    pub aleatorio: Aleatorio,
+}
+
+impl<Gene, Evol> GeneticoMultiobjetivo<Gene, Evol>
+    where Evol: EvolucaoMultiobjetivo<Gene>
+{
+    pub fn criar(pop: PopulacaoMultiobjetivo<Gene>, evolucao: Evol, geracoes: usize) -> Self {
+        GeneticoMultiobjetivo {
+            populacao: pop,
+            evolucao,
+            geracoes,
+            aleatorio: Aleatorio::criar(),
+        }
+    }
+
+    /// Define a semente do gerador de números aleatórios usado nas evoluções.
+    pub fn semente(&mut self, semente: u64) {
+        self.aleatorio = Aleatorio::semente(semente);
+    }
+
+    /// Busca pela fronteira de Pareto do problema
+    pub fn buscar_fronteira(self) -> Vec<IndividuoMultiobjetivo<Gene>> {
+
+        let mut pop = self.populacao;
+        let mut aleatorio = self.aleatorio;
+        pop.classificar();
+
+        for ger in 0..self.geracoes {
+            trace!("Geração {}", ger + 1);
+            pop = self.evolucao.evoluir(&mut pop, &mut aleatorio);
+        }
+
+        pop.fronteira_pareto()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use populacao::{CriadorIndividuos, Operacao};
+
+    /// Problema de Schaffer: minimizar x² e (x - 2)². A fronteira de Pareto está em [0, 2].
+    struct Schaffer;
+
+    impl AptidaoMultiobjetivo<f64> for Schaffer {
+        fn calcular_aptidoes(&self, x: &f64) -> Vec<f64> {
+            vec![x * x, (x - 2.0) * (x - 2.0)]
+        }
+    }
+
+    impl CriadorIndividuos<f64> for Schaffer {
+        fn criar(&self, aleatorio: &mut Aleatorio) -> f64 {
+            aleatorio.intervalo(-10.0, 10.0)
+        }
+    }
+
+    impl Cruzador<f64> for Schaffer {
+        fn cruzar(&self, aleatorio: &mut Aleatorio, primeiro: &f64, segundo: &f64) -> (f64, f64) {
+            let peso = aleatorio.chance();
+            (peso * primeiro + (1.0 - peso) * segundo, peso * segundo + (1.0 - peso) * primeiro)
+        }
+    }
+
+    impl Mutagenico<f64> for Schaffer {
+        fn mutar(&self, gene: &f64, aleatorio: &mut Aleatorio) -> f64 {
+            gene + aleatorio.intervalo(-0.5, 0.5)
+        }
+    }
+
+    #[test]
+    fn deve_encontrar_fronteira_pareto() {
+        let mut aleatorio = Aleatorio::semente(3);
+        let pop = PopulacaoMultiobjetivo::criar(Schaffer,
+                                                &Schaffer,
+                                                40,
+                                                vec![Operacao::Min, Operacao::Min],
+                                                &mut aleatorio);
+
+        let mut genetico = GeneticoMultiobjetivo::criar(pop,
+                                                        Nsga2::criar(Schaffer, Schaffer, 0.9, 0.3),
+                                                        50);
+        genetico.aleatorio = aleatorio;
+
+        let fronteira = genetico.buscar_fronteira();
+
+        assert!(fronteira.len() > 10);
+        for ind in fronteira.iter() {
+            assert!(ind.genes > -0.1 && ind.genes < 2.1, "Fora da fronteira: {}", ind.genes);
+        }
+    }
+}
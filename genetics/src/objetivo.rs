@@ -1,20 +1,138 @@
 
+use std::any::type_name;
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
 use populacao::*;
 
+/// A situação da execução em uma geração, avaliada pelos objetivos.
+pub struct Situacao<'a, Gene: 'a> {
+    /// Número da geração, 0 é a população inicial
+    pub geracao: usize,
+    /// O indivíduo mais apto entre todas as populações
+    pub mais_apto: &'a Individuo<Gene>,
+    /// As populações em evolução, apenas uma no AG simples e uma por ilha no modelo de ilhas
+    pub populacoes: Vec<&'a Populacao<Gene>>,
+}
+
+impl<'a, Gene> Situacao<'a, Gene> {
+    /// Cria a situação de uma única população.
+    pub fn criar(geracao: usize, populacao: &'a Populacao<Gene>) -> Self {
+        Situacao {
+            geracao,
+            mais_apto: populacao.mais_apto(),
+            populacoes: vec![populacao],
+        }
+    }
+
+    /// Todos os indivíduos de todas as populações.
+    pub fn individuos(&self) -> impl Iterator<Item = &'a Individuo<Gene>> + '_ {
+        self.populacoes.iter().flat_map(|pop| pop.individuos.iter())
+    }
+
+    /// A operação usada na comparação das aptidões.
+    pub fn operacao(&self) -> &'a Operacao {
+        self.populacoes[0].operacao()
+    }
+
+    /// Quantidade de aptidões calculadas desde o início da execução.
+    pub fn avaliacoes(&self) -> usize {
+        self.populacoes.iter().map(|pop| pop.avaliacoes()).sum()
+    }
+
+    /// Desvio padrão da aptidão de todos os indivíduos.
+    pub fn desvio_padrao(&self) -> f64 {
+        let qtd = self.individuos().count() as f64;
+        if qtd < 2.0 {
+            return 0.0;
+        }
+
+        let media = self.individuos().map(|i| i.aptidao).sum::<f64>() / qtd;
+        let soma_quadrado_diff = self.individuos()
+                                     .map(|i| (i.aptidao - media).powi(2))
+                                     .sum::<f64>();
+
+        (soma_quadrado_diff / (qtd - 1.0)).sqrt()
+    }
+}
+
 pub trait Objetivo<Gene> {
-    fn satisfeito_por(&mut self, individuo: &Individuo<Gene>) -> bool;
+    /// Avalia se o objetivo foi atingido na situação atual. Chamado uma vez por geração.
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool;
+
+    /// Estado interno do objetivo, guardado quando a execução é salva. Objetivos sem estado não
+    /// precisam implementar.
+    fn estado(&self) -> Value {
+        Value::Null
+    }
+
+    /// Restaura o estado gerado por `estado`.
+    fn restaurar(&mut self, _estado: &Value) {}
+
+    /// Descrição do objetivo, usada para informar qual objetivo encerrou a execução.
+    fn descricao(&self) -> String {
+        type_name::<Self>().to_string()
+    }
+}
+
+/// Avalia todos os objetivos, sem interromper no primeiro satisfeito, de forma que os objetivos
+/// com estado, como o `NumeroMaximoIteracoes`, sejam atualizados em toda geração. Retorna os
+/// indices dos objetivos satisfeitos.
+fn avaliar_todos<Gene>(objetivos: &mut [Box<dyn Objetivo<Gene>>],
+                       situacao: &Situacao<Gene>)
+                       -> Vec<usize> {
+    let mut satisfeitos = Vec::new();
+    for (idx, obj) in objetivos.iter_mut().enumerate() {
+        if obj.satisfeito_por(situacao) {
+            satisfeitos.push(idx);
+        }
+    }
+    satisfeitos
 }
 
-/// O objetivo misto guarda uma relação de objetivos que se quer atingir.
+/// Descreve os objetivos satisfeitos ou todos quando nenhum foi satisfeito.
+fn descrever<Gene>(objetivos: &[Box<dyn Objetivo<Gene>>],
+                   satisfeitos: &[usize],
+                   separador: &str)
+                   -> String {
+    let descricoes: Vec<String> = if satisfeitos.is_empty() {
+        objetivos.iter().map(|obj| obj.descricao()).collect()
+    } else {
+        satisfeitos.iter().map(|idx| objetivos[*idx].descricao()).collect()
+    };
+    descricoes.join(separador)
+}
+
+fn estado_lista<Gene>(objetivos: &[Box<dyn Objetivo<Gene>>]) -> Value {
+    Value::Array(objetivos.iter().map(|obj| obj.estado()).collect())
+}
+
+fn restaurar_lista<Gene>(objetivos: &mut [Box<dyn Objetivo<Gene>>], estado: &Value) {
+    if let Some(estados) = estado.as_array() {
+        for (obj, estado) in objetivos.iter_mut().zip(estados) {
+            obj.restaurar(estado);
+        }
+    }
+}
+
+/// O objetivo misto guarda uma relação de objetivos que se quer atingir. Ele é satisfeito
+/// quando qualquer um dos objetivos é satisfeito.
 pub struct ObjetivoMisto<Gene> {
-    objetivos: Vec<Box<Objetivo<Gene>>>,
+    objetivos: Vec<Box<dyn Objetivo<Gene>>>,
+    /// Indices dos objetivos satisfeitos na última avaliação
+    satisfeitos: Vec<usize>,
 }
 
 /// Implementação básica do objetivo misto.
 impl<Gene> ObjetivoMisto<Gene> {
     /// Cria um novo objetivo misto vazio.
     pub fn criar() -> Self {
-        ObjetivoMisto { objetivos: Vec::new() }
+        ObjetivoMisto {
+            objetivos: Vec::new(),
+            satisfeitos: Vec::new(),
+        }
     }
 
     /// Adiciona um novo objetivo na lista de objetivos mistos.
@@ -28,21 +146,179 @@ impl<Gene> ObjetivoMisto<Gene> {
 /// Implementação do objetivo misto ao ser aplicado como objetivo na resolução de
 /// um problema de AG.
 impl<Gene> Objetivo<Gene> for ObjetivoMisto<Gene> {
-    /// Avalia se algum dos objetovos controlados pelo objetivo misto foi
-    /// satisfeito. Retorna assim true se qualquer um dos sobjetivos foi
-    /// atingido e para de avalidar a lista de objetivos.
-    fn satisfeito_por(&mut self, individuo: &Individuo<Gene>) -> bool {
-
-        for obj in self.objetivos.iter_mut() {
-            if obj.satisfeito_por(individuo) {
-                return true;
-            }
+    /// Avalia se algum dos objetivos controlados pelo objetivo misto foi
+    /// satisfeito. Todos os objetivos são avaliados, mesmo depois de um deles
+    /// ser satisfeito.
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool {
+        self.satisfeitos = avaliar_todos(&mut self.objetivos, situacao);
+        !self.satisfeitos.is_empty()
+    }
+
+    /// A descrição dos objetivos satisfeitos ou de todos quando nenhum foi satisfeito.
+    fn descricao(&self) -> String {
+        if self.satisfeitos.is_empty() {
+            descrever(&self.objetivos, &self.satisfeitos, " ou ")
+        } else {
+            descrever(&self.objetivos, &self.satisfeitos, " e ")
         }
+    }
 
-        return false;
+    fn estado(&self) -> Value {
+        estado_lista(&self.objetivos)
+    }
+
+    fn restaurar(&mut self, estado: &Value) {
+        restaurar_lista(&mut self.objetivos, estado);
+    }
+}
+
+/// Objetivo satisfeito apenas quando todos os seus objetivos são satisfeitos ao mesmo tempo.
+pub struct ObjetivoTodos<Gene> {
+    objetivos: Vec<Box<dyn Objetivo<Gene>>>,
+}
+
+impl<Gene> ObjetivoTodos<Gene> {
+    pub fn criar() -> Self {
+        ObjetivoTodos { objetivos: Vec::new() }
+    }
+
+    pub fn adicionar<Obj>(&mut self, objetivo: Obj)
+        where Obj: Objetivo<Gene> + 'static
+    {
+        self.objetivos.push(Box::new(objetivo));
+    }
+}
+
+impl<Gene> Objetivo<Gene> for ObjetivoTodos<Gene> {
+    /// Todos os objetivos são avaliados, mesmo depois de um deles não ser satisfeito.
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool {
+        avaliar_todos(&mut self.objetivos, situacao).len() == self.objetivos.len()
+    }
+
+    fn descricao(&self) -> String {
+        descrever(&self.objetivos, &[], " e ")
+    }
+
+    fn estado(&self) -> Value {
+        estado_lista(&self.objetivos)
+    }
+
+    fn restaurar(&mut self, estado: &Value) {
+        restaurar_lista(&mut self.objetivos, estado);
+    }
+}
+
+/// Objetivo satisfeito quando uma quantidade mínima dos seus objetivos é satisfeita ao mesmo
+/// tempo.
+pub struct ObjetivoAlgunsDe<Gene> {
+    objetivos: Vec<Box<dyn Objetivo<Gene>>>,
+    /// Quantidade de objetivos que devem ser satisfeitos
+    minimo: usize,
+    /// Indices dos objetivos satisfeitos na última avaliação
+    satisfeitos: Vec<usize>,
+}
+
+impl<Gene> ObjetivoAlgunsDe<Gene> {
+    /// Cria o objetivo que é satisfeito quando *minimo* dos objetivos adicionados são
+    /// satisfeitos.
+    pub fn criar(minimo: usize) -> Self {
+        ObjetivoAlgunsDe {
+            objetivos: Vec::new(),
+            minimo,
+            satisfeitos: Vec::new(),
+        }
+    }
+
+    pub fn adicionar<Obj>(&mut self, objetivo: Obj)
+        where Obj: Objetivo<Gene> + 'static
+    {
+        self.objetivos.push(Box::new(objetivo));
+    }
+}
+
+impl<Gene> Objetivo<Gene> for ObjetivoAlgunsDe<Gene> {
+    /// Todos os objetivos são avaliados, mesmo depois do mínimo ser atingido.
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool {
+        self.satisfeitos = avaliar_todos(&mut self.objetivos, situacao);
+        self.satisfeitos.len() >= self.minimo
+    }
+
+    fn descricao(&self) -> String {
+        if self.satisfeitos.len() >= self.minimo {
+            descrever(&self.objetivos, &self.satisfeitos, " e ")
+        } else {
+            format!("{} de ({})",
+                    self.minimo,
+                    descrever(&self.objetivos, &[], ", "))
+        }
+    }
+
+    fn estado(&self) -> Value {
+        estado_lista(&self.objetivos)
+    }
+
+    fn restaurar(&mut self, estado: &Value) {
+        restaurar_lista(&mut self.objetivos, estado);
+    }
+}
+
+/// Objetivo satisfeito quando o objetivo interno não é satisfeito.
+pub struct ObjetivoNegacao<Obj> {
+    objetivo: Obj,
+}
+
+impl<Obj> ObjetivoNegacao<Obj> {
+    pub fn criar(objetivo: Obj) -> Self {
+        ObjetivoNegacao { objetivo }
+    }
+}
+
+impl<Gene, Obj> Objetivo<Gene> for ObjetivoNegacao<Obj>
+    where Obj: Objetivo<Gene>
+{
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool {
+        !self.objetivo.satisfeito_por(situacao)
+    }
+
+    fn descricao(&self) -> String {
+        format!("Não ({})", self.objetivo.descricao())
+    }
+
+    fn estado(&self) -> Value {
+        self.objetivo.estado()
+    }
+
+    fn restaurar(&mut self, estado: &Value) {
+        self.objetivo.restaurar(estado);
+    }
+}
+
+/// Métodos para compor objetivos, por exemplo
+/// `ObjetivoIntervalo::criar(0.0, 1.0).e(NumeroMaximoIteracoes::criar(50))`. A negação é feita
+/// com `ObjetivoNegacao::criar`.
+pub trait CombinacaoObjetivos<Gene>: Objetivo<Gene> + Sized + 'static {
+    /// Objetivo satisfeito quando esse e *outro* são satisfeitos.
+    fn e<Obj>(self, outro: Obj) -> ObjetivoTodos<Gene>
+        where Obj: Objetivo<Gene> + 'static
+    {
+        let mut todos = ObjetivoTodos::criar();
+        todos.adicionar(self);
+        todos.adicionar(outro);
+        todos
+    }
+
+    /// Objetivo satisfeito quando esse ou *outro* é satisfeito.
+    fn ou<Obj>(self, outro: Obj) -> ObjetivoMisto<Gene>
+        where Obj: Objetivo<Gene> + 'static
+    {
+        let mut misto = ObjetivoMisto::criar();
+        misto.adicionar(self);
+        misto.adicionar(outro);
+        misto
     }
 }
 
+impl<Gene, Obj> CombinacaoObjetivos<Gene> for Obj where Obj: Objetivo<Gene> + 'static {}
 
 pub struct NumeroMaximoIteracoes {
     maximo: usize,
@@ -51,6 +327,7 @@ pub struct NumeroMaximoIteracoes {
 }
 
 impl NumeroMaximoIteracoes {
+    #[allow(clippy::redundant_field_names)]
     pub fn criar(maximo: usize) -> Self {
         NumeroMaximoIteracoes {
             maximo: maximo,
@@ -60,12 +337,27 @@ impl NumeroMaximoIteracoes {
 }
 
 impl<Gene> Objetivo<Gene> for NumeroMaximoIteracoes {
-    fn satisfeito_por(&mut self, _: &Individuo<Gene>) -> bool {
-        let eq = self.maximo == self.atual;
+    fn satisfeito_por(&mut self, _: &Situacao<Gene>) -> bool {
+        // Continua satisfeito depois do máximo, para ser combinado com outros objetivos
+        let atingido = self.atual >= self.maximo;
 
-        self.atual = self.atual + 1;
+        self.atual = self.atual.saturating_add(1);
 
-        eq
+        atingido
+    }
+
+    fn estado(&self) -> Value {
+        Value::from(self.atual)
+    }
+
+    fn restaurar(&mut self, estado: &Value) {
+        if let Some(atual) = estado.as_u64() {
+            self.atual = atual as usize;
+        }
+    }
+
+    fn descricao(&self) -> String {
+        format!("Número máximo de iterações ({})", self.maximo)
     }
 }
 
@@ -79,6 +371,7 @@ pub struct ObjetivoIntervalo {
 
 impl ObjetivoIntervalo {
     /// Cria uma nova instância com valores de intervalo mínimo e máximo.
+    #[allow(clippy::redundant_field_names)]
     pub fn criar(min: f64, max: f64) -> Self {
         ObjetivoIntervalo {
             min: min,
@@ -90,92 +383,377 @@ impl ObjetivoIntervalo {
 /// Implementação do ObjetivoIntervalo como um objetivo.
 impl<Gene> Objetivo<Gene> for ObjetivoIntervalo {
     /// Avalia se a aptidão do indivíduo de melhor aptidao está dentro da faixa procurada.
-    fn satisfeito_por(&mut self, ind: &Individuo<Gene>) -> bool {
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool {
 
+        let ind = situacao.mais_apto;
         self.min.lt(&ind.aptidao) && self.max.gt(&ind.aptidao)
 
     }
+
+    fn descricao(&self) -> String {
+        format!("Aptidão entre {} e {}", self.min, self.max)
+    }
+}
+
+/// Objetivo satisfeito quando a melhor aptidão não melhora por uma quantidade de gerações.
+pub struct ObjetivoEstagnacao {
+    /// Quantidade de gerações sem melhora
+    geracoes: usize,
+    /// Diferença mínima na aptidão para que seja considerada uma melhora
+    tolerancia: f64,
+    /// A melhor aptidão encontrada e a geração em que ela foi encontrada
+    melhor: Option<(f64, usize)>,
+}
+
+impl ObjetivoEstagnacao {
+    /// Cria o objetivo que para após *geracoes* gerações sem melhora maior que *tolerancia*.
+    pub fn criar(geracoes: usize, tolerancia: f64) -> Self {
+        ObjetivoEstagnacao {
+            geracoes,
+            tolerancia,
+            melhor: None,
+        }
+    }
 }
 
+impl<Gene> Objetivo<Gene> for ObjetivoEstagnacao {
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool {
+
+        let aptidao = situacao.mais_apto.aptidao;
+        let melhor = match self.melhor {
+            Some((melhor, geracao)) => {
+                let melhorou = situacao.operacao().compara(&aptidao, &melhor) ==
+                               Ordering::Greater &&
+                               (aptidao - melhor).abs() > self.tolerancia;
+                if melhorou {
+                    (aptidao, situacao.geracao)
+                } else {
+                    (melhor, geracao)
+                }
+            }
+            None => (aptidao, situacao.geracao),
+        };
+        self.melhor = Some(melhor);
+
+        situacao.geracao.saturating_sub(melhor.1) >= self.geracoes
+    }
+
+    fn estado(&self) -> Value {
+        match self.melhor {
+            Some((aptidao, geracao)) => {
+                Value::Array(vec![Value::from(aptidao), Value::from(geracao)])
+            }
+            None => Value::Null,
+        }
+    }
+
+    fn restaurar(&mut self, estado: &Value) {
+        if let (Some(aptidao), Some(geracao)) = (estado[0].as_f64(), estado[1].as_u64()) {
+            self.melhor = Some((aptidao, geracao as usize));
+        }
+    }
 
+    fn descricao(&self) -> String {
+        format!("Estagnação por {} gerações", self.geracoes)
+    }
+}
 
+/// Objetivo satisfeito quando o desvio padrão da aptidão da população fica abaixo de um valor,
+/// ou seja, quando os indivíduos convergiram.
+pub struct ObjetivoConvergencia {
+    /// Desvio padrão abaixo do qual a população é considerada convergida
+    epsilon: f64,
+}
+
+impl ObjetivoConvergencia {
+    pub fn criar(epsilon: f64) -> Self {
+        ObjetivoConvergencia { epsilon }
+    }
+}
+
+impl<Gene> Objetivo<Gene> for ObjetivoConvergencia {
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool {
+        situacao.desvio_padrao() < self.epsilon
+    }
+
+    fn descricao(&self) -> String {
+        format!("Desvio padrão da aptidão menor que {}", self.epsilon)
+    }
+}
+
+/// Objetivo satisfeito quando a quantidade de aptidões calculadas atinge um máximo.
+pub struct NumeroMaximoAvaliacoes {
+    maximo: usize,
+}
+
+impl NumeroMaximoAvaliacoes {
+    pub fn criar(maximo: usize) -> Self {
+        NumeroMaximoAvaliacoes { maximo }
+    }
+}
+
+impl<Gene> Objetivo<Gene> for NumeroMaximoAvaliacoes {
+    fn satisfeito_por(&mut self, situacao: &Situacao<Gene>) -> bool {
+        situacao.avaliacoes() >= self.maximo
+    }
+
+    fn descricao(&self) -> String {
+        format!("Número máximo de avaliações ({})", self.maximo)
+    }
+}
+
+/// Objetivo satisfeito após um tempo de execução. O tempo começa a contar na primeira avaliação
+/// do objetivo e o tempo já decorrido é mantido quando a execução é salva.
+pub struct ObjetivoTempo {
+    /// Tempo máximo de execução
+    limite: Duration,
+    /// Tempo decorrido antes do início atual, vindo de uma execução salva
+    anterior: Duration,
+    /// Momento da primeira avaliação
+    inicio: Option<Instant>,
+}
+
+impl ObjetivoTempo {
+    pub fn criar(limite: Duration) -> Self {
+        ObjetivoTempo {
+            limite,
+            anterior: Duration::from_secs(0),
+            inicio: None,
+        }
+    }
+
+    fn decorrido(&self) -> Duration {
+        match self.inicio {
+            Some(inicio) => self.anterior + inicio.elapsed(),
+            None => self.anterior,
+        }
+    }
+}
+
+impl<Gene> Objetivo<Gene> for ObjetivoTempo {
+    fn satisfeito_por(&mut self, _: &Situacao<Gene>) -> bool {
+        if self.inicio.is_none() {
+            self.inicio = Some(Instant::now());
+        }
+        self.decorrido() >= self.limite
+    }
+
+    fn estado(&self) -> Value {
+        Value::from(self.decorrido().as_secs_f64())
+    }
+
+    fn restaurar(&mut self, estado: &Value) {
+        if let Some(segundos) = estado.as_f64() {
+            self.anterior = Duration::from_secs_f64(segundos);
+            self.inicio = None;
+        }
+    }
+
+    fn descricao(&self) -> String {
+        format!("Tempo máximo de execução ({:?})", self.limite)
+    }
+}
 
 #[cfg(test)]
 mod test {
 
     use super::*;
-    use populacao::Individuo;
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     #[test]
+    #[allow(clippy::assign_op_pattern)]
     fn deve_respeitar_repeticao_maxima() {
+        let pop = cria_populacao(&vec![10], Operacao::Max);
         let mut nmi = NumeroMaximoIteracoes::criar(4);
         let mut qtd = 0;
-        while !nmi.satisfeito_por(&Individuo {
-            genes: 10,
-            aptidao: 1.0,
-        }) {
+        while !nmi.satisfeito_por(&Situacao::criar(qtd, &pop)) {
             qtd = qtd + 1;
         }
         assert_eq!(4, qtd);
     }
 
     #[test]
-    fn deve_parar_primeiro_objetivo_multiplo_satisfeito() {
+    fn deve_restaurar_estado_misto() {
+        let mut objetivo = ObjetivoMisto::criar();
+        objetivo.adicionar(NumeroMaximoIteracoes::criar(4));
+        objetivo.adicionar(ObjetivoIntervalo::criar(0.0, 1.0));
+
+        let pop = cria_populacao(&vec![5], Operacao::Max);
+        let situacao = Situacao::criar(0, &pop);
+        objetivo.satisfeito_por(&situacao);
+        objetivo.satisfeito_por(&situacao);
+        objetivo.satisfeito_por(&situacao);
+        let estado = objetivo.estado();
+
+        let mut restaurado = ObjetivoMisto::criar();
+        restaurado.adicionar(NumeroMaximoIteracoes::criar(4));
+        restaurado.adicionar(ObjetivoIntervalo::criar(0.0, 1.0));
+        restaurado.restaurar(&estado);
+
+        assert!(!restaurado.satisfeito_por(&situacao));
+        assert!(restaurado.satisfeito_por(&situacao));
+    }
+
+    #[test]
+    fn deve_descrever_objetivo_satisfeito() {
+        let mut objetivo = ObjetivoMisto::criar();
+        objetivo.adicionar(NumeroMaximoIteracoes::criar(1));
+        objetivo.adicionar(ObjetivoIntervalo::criar(0.0, 2.0));
+
+        assert_eq!("Número máximo de iterações (1) ou Aptidão entre 0 e 2",
+                   objetivo.descricao());
+
+        let pop = cria_populacao(&vec![1], Operacao::Max);
+        assert!(objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+        assert_eq!("Aptidão entre 0 e 2", objetivo.descricao());
+    }
+
+    #[test]
+    fn deve_combinar_objetivos() {
+        let pop = cria_populacao(&vec![1], Operacao::Max);
+
+        // Aptidão no intervalo e pelo menos 3 gerações
+        let mut objetivo = ObjetivoIntervalo::criar(0.0, 2.0).e(NumeroMaximoIteracoes::criar(3));
+        for ger in 0..3 {
+            assert!(!objetivo.satisfeito_por(&Situacao::criar(ger, &pop)));
+        }
+        assert!(objetivo.satisfeito_por(&Situacao::criar(3, &pop)));
+
+        let mut objetivo = ObjetivoNegacao::criar(ObjetivoIntervalo::criar(0.0, 2.0));
+        assert!(!objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+        assert_eq!("Não (Aptidão entre 0 e 2)", Objetivo::<usize>::descricao(&objetivo));
+
+        let mut objetivo = ObjetivoAlgunsDe::criar(2);
+        objetivo.adicionar(ObjetivoIntervalo::criar(0.0, 2.0));
+        objetivo.adicionar(ObjetivoIntervalo::criar(5.0, 9.0));
+        objetivo.adicionar(NumeroMaximoAvaliacoes::criar(1));
+        assert!(objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+        assert_eq!("Aptidão entre 0 e 2 e Número máximo de avaliações (1)",
+                   objetivo.descricao());
+    }
+
+    #[test]
+    fn deve_avaliar_todos_objetivos_misto() {
+        let pop = cria_populacao(&vec![1], Operacao::Max);
+        let mut objetivo = ObjetivoIntervalo::criar(0.0, 2.0).ou(NumeroMaximoIteracoes::criar(2));
+
+        // O contador é atualizado mesmo com o primeiro objetivo satisfeito
+        assert!(objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+        assert!(objetivo.satisfeito_por(&Situacao::criar(1, &pop)));
+        assert_eq!(Value::Array(vec![Value::Null, Value::from(2)]), objetivo.estado());
+    }
+
+    #[test]
+    fn deve_parar_apos_estagnacao() {
+        let mut objetivo = ObjetivoEstagnacao::criar(3, 0.5);
+        let evolucao = [1, 2, 2, 3, 3, 3, 3];
+        let mut satisfeito = Vec::new();
+
+        for (geracao, melhor) in evolucao.iter().enumerate() {
+            let pop = cria_populacao(&vec![*melhor], Operacao::Max);
+            satisfeito.push(objetivo.satisfeito_por(&Situacao::criar(geracao, &pop)));
+        }
+
+        assert_eq!(vec![false, false, false, false, false, false, true], satisfeito);
+
+        // Pequenas melhoras dentro da tolerância não contam
+        let mut objetivo = ObjetivoEstagnacao::criar(2, 1.5);
+        let pop = cria_populacao(&vec![1], Operacao::Min);
+        assert!(!objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+        let pop = cria_populacao(&vec![0], Operacao::Min);
+        assert!(!objetivo.satisfeito_por(&Situacao::criar(1, &pop)));
+        assert!(objetivo.satisfeito_por(&Situacao::criar(2, &pop)));
+    }
+
+    #[test]
+    fn deve_parar_na_convergencia() {
+        let mut objetivo = ObjetivoConvergencia::criar(1.0);
+
+        let pop = cria_populacao(&vec![1, 5, 9], Operacao::Max);
+        assert!(!objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+
+        let pop = cria_populacao(&vec![5, 5, 6], Operacao::Max);
+        assert!(objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+    }
+
+    #[test]
+    fn deve_respeitar_avaliacoes_e_tempo() {
+        let mut pop = cria_populacao(&vec![1, 2, 3], Operacao::Max);
+        let mut objetivo = NumeroMaximoAvaliacoes::criar(4);
+        assert!(!objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+        pop.adicionar(4);
+        assert!(objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+
+        let mut objetivo = ObjetivoTempo::criar(Duration::from_secs(3600));
+        assert!(!objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+
+        // O tempo decorrido de uma execução salva continua contando
+        Objetivo::<usize>::restaurar(&mut objetivo, &Value::from(3600.0));
+        assert!(objetivo.satisfeito_por(&Situacao::criar(0, &pop)));
+    }
+
+    #[test]
+    fn deve_combinar_intervalo_atingido_apos_maximo_iteracoes() {
+        let longe = cria_populacao(&vec![10], Operacao::Max);
+        let perto = cria_populacao(&vec![1], Operacao::Max);
+
+        // O intervalo só é atingido depois do número máximo de iterações
+        let mut objetivo = ObjetivoIntervalo::criar(0.0, 2.0).e(NumeroMaximoIteracoes::criar(2));
+        for ger in 0..5 {
+            assert!(!objetivo.satisfeito_por(&Situacao::criar(ger, &longe)));
+        }
+        assert!(objetivo.satisfeito_por(&Situacao::criar(5, &perto)));
+        assert!(objetivo.satisfeito_por(&Situacao::criar(6, &perto)));
+    }
+
+    #[test]
+    fn deve_avaliar_todos_objetivos_multiplos_satisfeitos() {
+        let chamados: Vec<_> = (0..3).map(|_| Rc::new(Cell::new(false))).collect();
         let mut objetivo = ObjetivoMisto::criar();
-        objetivo.adicionar(ObjetivoTeste {
-            valor: true,
-            chamado: false,
-        });
-        objetivo.adicionar(ObjetivoTeste {
-            valor: true,
-            chamado: false,
-        });
-        objetivo.adicionar(ObjetivoTeste {
-            valor: true,
-            chamado: false,
-        });
-
-        let res = objetivo.satisfeito_por(&Individuo {
-            genes: 10,
-            aptidao: 1.0,
-        });
-
-        assert_eq!(true, res);
+        for chamado in chamados.iter() {
+            objetivo.adicionar(ObjetivoTeste {
+                valor: true,
+                chamado: chamado.clone(),
+            });
+        }
+
+        let pop = cria_populacao(&vec![10], Operacao::Max);
+        let res = objetivo.satisfeito_por(&Situacao::criar(0, &pop));
+
+        assert!(res);
+        assert!(chamados.iter().all(|chamado| chamado.get()));
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn deve_validar_objetivos_multiplos() {
 
+        let chamados: Vec<_> = (0..3).map(|_| Rc::new(Cell::new(false))).collect();
         let mut objetivo = ObjetivoMisto::criar();
-        objetivo.adicionar(ObjetivoTeste {
-            valor: false,
-            chamado: false,
-        });
-        objetivo.adicionar(ObjetivoTeste {
-            valor: false,
-            chamado: false,
-        });
-        objetivo.adicionar(ObjetivoTeste {
-            valor: false,
-            chamado: false,
-        });
-
-        let res = objetivo.satisfeito_por(&Individuo {
-            genes: 10,
-            aptidao: 1.0,
-        });
+        for chamado in chamados.iter() {
+            objetivo.adicionar(ObjetivoTeste {
+                valor: false,
+                chamado: chamado.clone(),
+            });
+        }
+
+        let pop = cria_populacao(&vec![10], Operacao::Max);
+        let res = objetivo.satisfeito_por(&Situacao::criar(0, &pop));
         assert_eq!(false, res);
+        assert!(chamados.iter().all(|chamado| chamado.get()));
 
     }
 
     struct ObjetivoTeste {
         valor: bool,
-        chamado: bool,
+        chamado: Rc<Cell<bool>>,
     }
 
     impl Objetivo<usize> for ObjetivoTeste {
-        fn satisfeito_por(&mut self, _: &Individuo<usize>) -> bool {
-            self.chamado = true;
+        #[allow(clippy::needless_return)]
+        fn satisfeito_por(&mut self, _: &Situacao<usize>) -> bool {
+            self.chamado.set(true);
 
             return self.valor;
         }
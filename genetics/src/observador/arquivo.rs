@@ -0,0 +1,351 @@
+//! # Observadores em arquivo
+//!
+//! Gravam as estatísticas de cada geração em qualquer `Write`, como um arquivo, para serem lidas
+//! depois por scripts de análise. Antes das gerações é gravado um cabeçalho com os parâmetros da
+//! execução.
+
+use std::io;
+use std::io::Write;
+use std::time::Instant;
+
+use serde::Serialize;
+use serde_json;
+use serde_json::{Map, Value};
+
+use diversidade::Diversidade;
+use observador::ObservadorEvolucao;
+use populacao::Populacao;
+
+/// Informação de uma geração que pode ser gravada.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Coluna {
+    /// Número da geração, 0 é a população inicial
+    Geracao,
+    /// Melhor aptidão
+    Melhor,
+    /// Pior aptidão
+    Pior,
+    /// Aptidão média
+    Media,
+    /// Desvio padrão da aptidão
+    Desvio,
+    /// Mediana da aptidão
+    Mediana,
+    /// Quantidade de aptidões calculadas até a geração
+    Avaliacoes,
+    /// Segundos decorridos desde o início da busca
+    Tempo,
+    /// Distância média entre os indivíduos, veja `Populacao::medir_diversidade`
+    DistanciaMedia,
+    /// Distância média ao centróide, veja `Populacao::medir_diversidade_posicional`
+    DistanciaCentroide,
+    /// Entropia média das posições, veja `Populacao::medir_diversidade_posicional`
+    Entropia,
+}
+
+impl Coluna {
+    /// As colunas de aptidão, gravadas por padrão.
+    pub fn aptidao() -> Vec<Coluna> {
+        vec![Coluna::Geracao,
+             Coluna::Melhor,
+             Coluna::Pior,
+             Coluna::Media,
+             Coluna::Desvio,
+             Coluna::Mediana,
+             Coluna::Avaliacoes,
+             Coluna::Tempo]
+    }
+
+    /// As colunas de diversidade. Ficam vazias quando a população não mede a diversidade.
+    pub fn diversidade() -> Vec<Coluna> {
+        vec![Coluna::DistanciaMedia, Coluna::DistanciaCentroide, Coluna::Entropia]
+    }
+
+    /// O nome da coluna usado no cabeçalho do CSV e nas chaves do JSON.
+    pub fn nome(&self) -> &'static str {
+        match *self {
+            Coluna::Geracao => "geracao",
+            Coluna::Melhor => "melhor",
+            Coluna::Pior => "pior",
+            Coluna::Media => "media",
+            Coluna::Desvio => "desvio",
+            Coluna::Mediana => "mediana",
+            Coluna::Avaliacoes => "avaliacoes",
+            Coluna::Tempo => "tempo",
+            Coluna::DistanciaMedia => "distancia_media",
+            Coluna::DistanciaCentroide => "distancia_centroide",
+            Coluna::Entropia => "entropia",
+        }
+    }
+
+    fn valor<Gene>(&self,
+                   geracao: usize,
+                   pop: &Populacao<Gene>,
+                   diversidade: &Option<Diversidade>,
+                   tempo: f64)
+                   -> Value {
+        match *self {
+            Coluna::Geracao => Value::from(geracao),
+            Coluna::Melhor => Value::from(pop.melhor_aptidao()),
+            Coluna::Pior => Value::from(pop.pior_aptidao()),
+            Coluna::Media => Value::from(pop.aptidao_media()),
+            Coluna::Desvio => Value::from(pop.desvio_padrao_aptidao()),
+            Coluna::Mediana => Value::from(pop.mediana_aptidao()),
+            Coluna::Avaliacoes => Value::from(pop.avaliacoes()),
+            Coluna::Tempo => Value::from(tempo),
+            Coluna::DistanciaMedia => Value::from(diversidade.as_ref().map(|d| d.distancia_media)),
+            Coluna::DistanciaCentroide => {
+                Value::from(diversidade.as_ref().and_then(|d| d.distancia_centroide))
+            }
+            Coluna::Entropia => Value::from(diversidade.as_ref().and_then(|d| d.entropia)),
+        }
+    }
+}
+
+/// Configuração comum aos observadores em arquivo.
+struct Registro {
+    colunas: Vec<Coluna>,
+    parametros: Vec<(String, Value)>,
+    inicio: Option<Instant>,
+}
+
+impl Registro {
+    fn criar() -> Self {
+        Registro {
+            colunas: Coluna::aptidao(),
+            parametros: Vec::new(),
+            inicio: None,
+        }
+    }
+
+    fn parametro<V: Serialize>(&mut self, nome: &str, valor: V) {
+        let valor = serde_json::to_value(valor).unwrap_or(Value::Null);
+        self.parametros.push((nome.to_string(), valor));
+    }
+
+    /// Os valores das colunas na geração informada.
+    fn valores<Gene>(&mut self, geracao: usize, pop: &Populacao<Gene>) -> Vec<Value> {
+        let tempo = match self.inicio {
+            Some(inicio) => inicio.elapsed().as_secs_f64(),
+            None => {
+                self.inicio = Some(Instant::now());
+                0.0
+            }
+        };
+
+        // A diversidade tem custo quadrático, é calculada apenas quando gravada
+        let diversidade = if self.colunas.iter().any(|c| Coluna::diversidade().contains(c)) {
+            pop.diversidade()
+        } else {
+            None
+        };
+
+        self.colunas.iter().map(|coluna| coluna.valor(geracao, pop, &diversidade, tempo)).collect()
+    }
+}
+
+/// Texto de um valor sem as aspas usadas pelo JSON nos textos. Valores ausentes ficam vazios.
+fn texto(valor: &Value) -> String {
+    match *valor {
+        Value::String(ref texto) => texto.clone(),
+        Value::Null => String::new(),
+        ref outro => outro.to_string(),
+    }
+}
+
+/// Observador que grava as estatísticas de cada geração em CSV. Os parâmetros são gravados
+/// antes do cabeçalho em linhas iniciadas por `#`.
+pub struct ObservadorCsv<W: Write> {
+    saida: W,
+    registro: Registro,
+    separador: String,
+}
+
+impl<W: Write> ObservadorCsv<W> {
+    /// Cria o observador gravando as colunas de aptidão separadas por `;`.
+    pub fn criar(saida: W) -> Self {
+        ObservadorCsv {
+            saida,
+            registro: Registro::criar(),
+            separador: ";".to_string(),
+        }
+    }
+
+    /// Define as colunas gravadas e a ordem delas.
+    pub fn colunas(&mut self, colunas: Vec<Coluna>) {
+        self.registro.colunas = colunas;
+    }
+
+    /// Adiciona um parâmetro da execução ao cabeçalho.
+    pub fn parametro<V: Serialize>(&mut self, nome: &str, valor: V) {
+        self.registro.parametro(nome, valor);
+    }
+
+    /// Define o separador das colunas.
+    pub fn separador(&mut self, separador: &str) {
+        self.separador = separador.to_string();
+    }
+
+    /// Devolve a saída onde as gerações foram gravadas.
+    pub fn finalizar(self) -> W {
+        self.saida
+    }
+
+    fn gravar_cabecalho(&mut self) -> io::Result<()> {
+        for (nome, valor) in self.registro.parametros.iter() {
+            writeln!(self.saida, "# {}: {}", nome, texto(valor))?;
+        }
+        let nomes: Vec<&str> = self.registro.colunas.iter().map(|c| c.nome()).collect();
+        writeln!(self.saida, "{}", nomes.join(&self.separador))
+    }
+
+    fn gravar<Gene>(&mut self, geracao: usize, pop: &Populacao<Gene>) -> io::Result<()> {
+        let valores: Vec<String> = self.registro
+                                       .valores(geracao, pop)
+                                       .iter()
+                                       .map(texto)
+                                       .collect();
+        writeln!(self.saida, "{}", valores.join(&self.separador))?;
+        self.saida.flush()
+    }
+}
+
+impl<Gene, W: Write> ObservadorEvolucao<Gene> for ObservadorCsv<W> {
+    fn inicio(&mut self, pop: &Populacao<Gene>) {
+        if let Err(erro) = self.gravar_cabecalho().and_then(|_| self.gravar(0, pop)) {
+            error!("Falha ao gravar o CSV: {}", erro);
+        }
+    }
+
+    fn geracao(&mut self, geracao: usize, pop: &Populacao<Gene>) {
+        if let Err(erro) = self.gravar(geracao, pop) {
+            error!("Falha ao gravar a geração {} no CSV: {}", geracao, erro);
+        }
+    }
+}
+
+/// Observador que grava um objeto JSON por linha. A primeira linha contém os parâmetros da
+/// execução, em `{"parametros": {...}}`, e as seguintes as colunas de cada geração.
+pub struct ObservadorJsonLinhas<W: Write> {
+    saida: W,
+    registro: Registro,
+}
+
+impl<W: Write> ObservadorJsonLinhas<W> {
+    /// Cria o observador gravando as colunas de aptidão.
+    pub fn criar(saida: W) -> Self {
+        ObservadorJsonLinhas {
+            saida,
+            registro: Registro::criar(),
+        }
+    }
+
+    /// Define as colunas gravadas.
+    pub fn colunas(&mut self, colunas: Vec<Coluna>) {
+        self.registro.colunas = colunas;
+    }
+
+    /// Adiciona um parâmetro da execução à primeira linha.
+    pub fn parametro<V: Serialize>(&mut self, nome: &str, valor: V) {
+        self.registro.parametro(nome, valor);
+    }
+
+    /// Devolve a saída onde as gerações foram gravadas.
+    pub fn finalizar(self) -> W {
+        self.saida
+    }
+
+    fn gravar_linha(&mut self, linha: &Value) -> io::Result<()> {
+        serde_json::to_writer(&mut self.saida, linha)?;
+        writeln!(self.saida)?;
+        self.saida.flush()
+    }
+
+    fn gravar<Gene>(&mut self, geracao: usize, pop: &Populacao<Gene>) -> io::Result<()> {
+        let valores = self.registro.valores(geracao, pop);
+        let mut linha = Map::new();
+        for (coluna, valor) in self.registro.colunas.iter().zip(valores) {
+            linha.insert(coluna.nome().to_string(), valor);
+        }
+        self.gravar_linha(&Value::Object(linha))
+    }
+}
+
+impl<Gene, W: Write> ObservadorEvolucao<Gene> for ObservadorJsonLinhas<W> {
+    fn inicio(&mut self, pop: &Populacao<Gene>) {
+        let mut cabecalho = Map::new();
+        let parametros = self.registro.parametros.iter().cloned().collect();
+        cabecalho.insert("parametros".to_string(), Value::Object(parametros));
+
+        if let Err(erro) = self.gravar_linha(&Value::Object(cabecalho))
+                               .and_then(|_| self.gravar(0, pop)) {
+            error!("Falha ao gravar o JSON: {}", erro);
+        }
+    }
+
+    fn geracao(&mut self, geracao: usize, pop: &Populacao<Gene>) {
+        if let Err(erro) = self.gravar(geracao, pop) {
+            error!("Falha ao gravar a geração {} no JSON: {}", geracao, erro);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use populacao::*;
+
+    #[test]
+    fn deve_gravar_csv() {
+        let mut observador = ObservadorCsv::criar(Vec::new());
+        observador.colunas(vec![Coluna::Geracao, Coluna::Melhor, Coluna::Pior]);
+        observador.parametro("mutacao", 0.1);
+        observador.parametro("seletor", "torneio");
+
+        let pop = cria_populacao(&vec![1, 2], Operacao::Max);
+        ObservadorEvolucao::<usize>::inicio(&mut observador, &pop);
+        observador.geracao(1, &cria_populacao(&vec![3, 2], Operacao::Max));
+
+        let texto = String::from_utf8(observador.finalizar()).unwrap();
+        assert_eq!("# mutacao: 0.1\n# seletor: torneio\ngeracao;melhor;pior\n0;2.0;1.0\n\
+                    1;3.0;2.0\n",
+                   texto);
+    }
+
+    #[test]
+    fn deve_gravar_diversidade() {
+        use diversidade::DistanciaHamming;
+
+        let mut observador = ObservadorCsv::criar(Vec::new());
+        let mut colunas = vec![Coluna::Geracao];
+        colunas.extend(Coluna::diversidade());
+        observador.colunas(colunas);
+
+        let mut pop = cria_populacao(&vec![1, 2], Operacao::Max);
+        ObservadorEvolucao::<usize>::inicio(&mut observador, &pop);
+        pop.medir_diversidade(DistanciaHamming);
+        observador.geracao(1, &pop);
+
+        let texto = String::from_utf8(observador.finalizar()).unwrap();
+        assert_eq!("geracao;distancia_media;distancia_centroide;entropia\n0;;;\n1;2.0;;\n",
+                   texto);
+    }
+
+    #[test]
+    fn deve_gravar_json_linhas() {
+        let mut observador = ObservadorJsonLinhas::criar(Vec::new());
+        observador.colunas(vec![Coluna::Geracao, Coluna::Avaliacoes]);
+        observador.parametro("populacao", 2);
+
+        let pop = cria_populacao(&vec![1, 2], Operacao::Max);
+        ObservadorEvolucao::<usize>::inicio(&mut observador, &pop);
+
+        let texto = String::from_utf8(observador.finalizar()).unwrap();
+        let linhas: Vec<Value> = texto.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(2, linhas.len());
+        assert_eq!(Value::from(2), linhas[0]["parametros"]["populacao"]);
+        assert_eq!(Value::from(0), linhas[1]["geracao"]);
+        assert_eq!(Value::from(2), linhas[1]["avaliacoes"]);
+    }
+}
@@ -0,0 +1,87 @@
+//! Observadores da evolução, usados para acompanhar a execução do AG.
+
+/// Observadores que gravam as estatísticas de cada geração em arquivos CSV ou JSON
+pub mod arquivo;
+
+pub use self::arquivo::*;
+
+use populacao::*;
+use resultado::Resultado;
+
+/// Interface que permite observar o processo de evolução dos indivíduos no AG
+pub trait ObservadorEvolucao<Gene> {
+    /// Método chamado no inicio da busca pela solução.
+    fn inicio(&mut self, pop: &Populacao<Gene>);
+    fn geracao(&mut self, geracao: usize, pop: &Populacao<Gene>);
+
+    /// Método chamado quando a execução termina, com o resultado encontrado.
+    fn fim(&mut self, _resultado: &Resultado<Gene>) {}
+}
+
+pub struct ObservadorEvolucaoVazio;
+
+impl<Gene> ObservadorEvolucao<Gene> for ObservadorEvolucaoVazio {
+    fn inicio(&mut self, _: &Populacao<Gene>) {}
+    fn geracao(&mut self, _: usize, _: &Populacao<Gene>) {}
+}
+
+/// Observador que repassa os eventos para vários observadores, na ordem em que foram
+/// adicionados.
+pub struct ObservadorMisto<Gene> {
+    observadores: Vec<Box<dyn ObservadorEvolucao<Gene>>>,
+}
+
+impl<Gene> ObservadorMisto<Gene> {
+    /// Cria um observador misto vazio.
+    pub fn criar() -> Self {
+        ObservadorMisto { observadores: Vec::new() }
+    }
+
+    /// Adiciona um observador na lista.
+    pub fn adicionar<Obs>(&mut self, observador: Obs)
+        where Obs: ObservadorEvolucao<Gene> + 'static
+    {
+        self.observadores.push(Box::new(observador));
+    }
+}
+
+impl<Gene> ObservadorEvolucao<Gene> for ObservadorMisto<Gene> {
+    fn inicio(&mut self, pop: &Populacao<Gene>) {
+        for observador in self.observadores.iter_mut() {
+            observador.inicio(pop);
+        }
+    }
+
+    fn geracao(&mut self, geracao: usize, pop: &Populacao<Gene>) {
+        for observador in self.observadores.iter_mut() {
+            observador.geracao(geracao, pop);
+        }
+    }
+
+    fn fim(&mut self, resultado: &Resultado<Gene>) {
+        for observador in self.observadores.iter_mut() {
+            observador.fim(resultado);
+        }
+    }
+}
+
+pub struct ObservadorEvolucaoImprimeAptidao;
+
+impl<Gene> ObservadorEvolucao<Gene> for ObservadorEvolucaoImprimeAptidao {
+    fn inicio(&mut self, pop: &Populacao<Gene>) {
+        println!("Geracao;Maior;Menor;Media;Desvio");
+        println!("0;{};{};{};{}",
+                 pop.melhor_aptidao(),
+                 pop.pior_aptidao(),
+                 pop.aptidao_media(),
+                 pop.desvio_aptidao());
+    }
+    fn geracao(&mut self, geracao: usize, pop: &Populacao<Gene>) {
+        println!("{};{};{};{};{}",
+                 geracao,
+                 pop.melhor_aptidao(),
+                 pop.pior_aptidao(),
+                 pop.aptidao_media(),
+                 pop.desvio_aptidao());
+    }
+}
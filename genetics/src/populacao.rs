@@ -3,12 +3,15 @@
 //! Módulo com lógica de utilização da população no Algoritmo Genético
 //!
 
-use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 use std::cmp::*;
 
 use aptidao::*;
 use aleatorio::*;
+use diversidade::*;
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Individuo<T> {
     pub genes: T,
     pub aptidao: f64,
@@ -40,6 +43,7 @@ impl Operacao {
     /// Minimizacao
     ///     a = 10, b = 8  => Ordering::Less
     ///     a = 10, b = 11 => Ordering::Greater
+    #[allow(clippy::match_ref_pats, clippy::needless_borrow)]
     pub fn compara(&self, a: &f64, b: &f64) -> Ordering {
         match self {
             &Operacao::Max => a.partial_cmp(&b).unwrap(),
@@ -69,80 +73,136 @@ impl Operacao {
 /// População
 pub struct Populacao<T> {
     /// Referencia para uma implementação que consegue calcular a aptidão para um tipo T
-    pub aptidao: Rc<Aptidao<T>>,
+    pub aptidao: Arc<dyn Aptidao<T> + Send + Sync>,
     /// A lista de indivíduos dessa populaçao
     pub individuos: Vec<Individuo<T>>,
     /// O tipo de operacão dessa população
     operacao: Operacao,
+    /// Quantidade de threads usadas no cálculo da aptidão de lotes de indivíduos
+    threads: usize,
+    /// Quantidade de aptidões calculadas, compartilhada entre as gerações
+    avaliacoes: Arc<AtomicUsize>,
+    /// Cálculo da diversidade, configurado com `medir_diversidade`
+    diversidade: Option<Arc<MedidaDiversidade<T>>>,
+    /// Geração dessa população
+    geracao: usize,
 }
 
+/// Função que calcula a diversidade dos indivíduos de uma população.
+type MedidaDiversidade<Gene> = dyn Fn(&[Individuo<Gene>]) -> Diversidade + Send + Sync;
+
 /// Implementação dos métodos da população
 impl<Gene> Populacao<Gene> {
     /// Cria uma nova população sem nenhum indivíduo
+    #[allow(clippy::redundant_field_names)]
     pub fn criar_vazia<Apt>(aptidao: Apt, operacao: Operacao) -> Self
-        where Apt: Aptidao<Gene> + 'static
+        where Apt: Aptidao<Gene> + Send + Sync + 'static
     {
         Populacao {
-            aptidao: Rc::new(aptidao),
+            aptidao: Arc::new(aptidao),
             individuos: Vec::new(),
             operacao: operacao,
+            threads: 1,
+            avaliacoes: Arc::new(AtomicUsize::new(0)),
+            diversidade: None,
+            geracao: 0,
         }
     }
 
     /// Cria uma população com um conjunto de indivíduos.
+    #[allow(clippy::extra_unused_lifetimes)]
     pub fn criar<'a, Apt, CIA>(aptidao: Apt,
                                criador: &CIA,
                                quantidade: usize,
                                operacao: Operacao)
                                -> Self
-        where Apt: Aptidao<Gene> + 'static,
-              CIA: CriadorIndividuos<Gene>
+        where Apt: Aptidao<Gene> + Send + Sync + 'static,
+              CIA: CriadorIndividuos<Gene>,
+              Gene: Sync
+    {
+        Populacao::criar_com_aleatorio(aptidao,
+                                       criador,
+                                       quantidade,
+                                       operacao,
+                                       &mut Aleatorio::criar())
+    }
+
+    /// Cria uma população com um conjunto de indivíduos usando o gerador de números aleatórios
+    /// informado. Com um gerador criado a partir de uma semente a população criada é sempre a
+    /// mesma.
+    pub fn criar_com_aleatorio<Apt, CIA>(aptidao: Apt,
+                                         criador: &CIA,
+                                         quantidade: usize,
+                                         operacao: Operacao,
+                                         aleatorio: &mut Aleatorio)
+                                         -> Self
+        where Apt: Aptidao<Gene> + Send + Sync + 'static,
+              CIA: CriadorIndividuos<Gene>,
+              Gene: Sync
+    {
+        Populacao::criar_paralela(aptidao, criador, quantidade, operacao, 1, aleatorio)
+    }
+
+    /// Cria uma população com um conjunto de indivíduos calculando a aptidão da população
+    /// inicial com *threads* threads, que também são usadas nas próximas gerações. Os genes são
+    /// criados antes, em sequência, portanto a população é a mesma para qualquer quantidade de
+    /// threads.
+    pub fn criar_paralela<Apt, CIA>(aptidao: Apt,
+                                    criador: &CIA,
+                                    quantidade: usize,
+                                    operacao: Operacao,
+                                    threads: usize,
+                                    aleatorio: &mut Aleatorio)
+                                    -> Self
+        where Apt: Aptidao<Gene> + Send + Sync + 'static,
+              CIA: CriadorIndividuos<Gene>,
+              Gene: Sync
     {
         debug!("Iniciando população");
-        let mut aleatorio = Aleatorio::criar();
-        let mut individuos = Vec::new();
-        for _ in 0..quantidade {
-            let genes = criador.criar(&mut aleatorio);
-            let apt = aptidao.calcular_aptidao(&genes);
-            let ind = Individuo {
-                genes: genes,
-                aptidao: apt,
-            };
-            individuos.push(ind);
-        }
+        let genes = (0..quantidade).map(|_| criador.criar(aleatorio)).collect();
+
+        let mut populacao = Populacao::criar_vazia(aptidao, operacao);
+        populacao.avaliacao_paralela(threads);
+        populacao.adicionar_lote(genes);
 
         trace!("População inicial criada");
-        Populacao {
-            aptidao: Rc::new(aptidao),
-            individuos: individuos,
-            operacao: operacao,
-        }
+        populacao
     }
 
     /// Busca na populacao o individuo mais apto.
     pub fn remover_mais_apto(&mut self) -> Individuo<Gene> {
+        let idx = self.indice_mais_apto();
+        self.individuos.remove(idx)
+    }
+
+    /// O indivíduo mais apto da população, sem removê-lo.
+    pub fn mais_apto(&self) -> &Individuo<Gene> {
+        &self.individuos[self.indice_mais_apto()]
+    }
+
+    fn indice_mais_apto(&self) -> usize {
 
         // Pegando o index do elemento mais apto dessa geração
         let mut idx = 0;
-        {
-            let mut maior = &self.individuos[0];
-            for i in 1..self.individuos.len() {
-                let outro = &self.individuos[i];
-                let comp = self.operacao.compara(&maior.aptidao, &outro.aptidao);
-
-                if comp == Ordering::Less {
-                    maior = outro;
-                    idx = i;
-                }
+        let mut maior = &self.individuos[0];
+        for i in 1..self.individuos.len() {
+            let outro = &self.individuos[i];
+            let comp = self.operacao.compara(&maior.aptidao, &outro.aptidao);
+
+            if comp == Ordering::Less {
+                maior = outro;
+                idx = i;
             }
         }
 
-        self.individuos.remove(idx)
+        idx
     }
 
+    #[allow(clippy::redundant_field_names)]
     pub fn adicionar(&mut self, genes: Gene) {
 
         let aptidao = self.aptidao.calcular_aptidao(&genes);
+        self.avaliacoes.fetch_add(1, AtomicOrdering::Relaxed);
 
         self.individuos.push(Individuo {
             aptidao: aptidao,
@@ -151,6 +211,26 @@ impl<Gene> Populacao<Gene> {
 
     }
 
+    /// Adiciona um lote de indivíduos de uma só vez. A aptidão do lote é calculada em paralelo
+    /// quando a população foi configurada com `avaliacao_paralela`.
+    pub fn adicionar_lote(&mut self, genes: Vec<Gene>)
+        where Gene: Sync
+    {
+        let aptidoes = calcular_aptidoes(&*self.aptidao, &genes, self.threads);
+        self.avaliacoes.fetch_add(aptidoes.len(), AtomicOrdering::Relaxed);
+
+        self.individuos.extend(genes.into_iter()
+                                    .zip(aptidoes)
+                                    .map(|(genes, aptidao)| Individuo { aptidao, genes }));
+    }
+
+    /// Define a quantidade de threads usadas no cálculo da aptidão dos lotes de indivíduos. As
+    /// novas gerações preparadas a partir dessa população herdam essa configuração.
+    pub fn avaliacao_paralela(&mut self, threads: usize) {
+        self.threads = threads;
+    }
+
+    #[allow(clippy::toplevel_ref_arg)]
     pub fn manter_mais_aptos(&mut self, quantiade: usize) {
 
         let ref operacao = self.operacao;
@@ -174,14 +254,47 @@ impl<Gene> Populacao<Gene> {
         self.individuos.append(&mut inds);
     }
 
+    /// Quantidade de aptidões calculadas por essa população e pelas gerações criadas a partir
+    /// dela com `preparar_nova_geracao`.
+    pub fn avaliacoes(&self) -> usize {
+        self.avaliacoes.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Altera a contagem de aptidões calculadas, usado ao continuar uma execução salva.
+    pub fn definir_avaliacoes(&mut self, avaliacoes: usize) {
+        self.avaliacoes.store(avaliacoes, AtomicOrdering::Relaxed);
+    }
+
+    /// A geração dessa população. É definida por `Genetico` e `Ilhas` antes de cada evolução,
+    /// permitindo que operadores mudem de comportamento ao longo da execução.
+    pub fn geracao(&self) -> usize {
+        self.geracao
+    }
+
+    /// Altera a geração dessa população, as novas gerações preparadas a partir dela começam com
+    /// a mesma geração.
+    pub fn definir_geracao(&mut self, geracao: usize) {
+        self.geracao = geracao;
+    }
+
+    /// O tipo de operação dessa população
+    pub fn operacao(&self) -> &Operacao {
+        &self.operacao
+    }
+
     pub fn preparar_nova_geracao(&self) -> Self {
         Populacao {
             aptidao: self.aptidao.clone(),
             individuos: Vec::new(),
             operacao: self.operacao.clone(),
+            threads: self.threads,
+            avaliacoes: self.avaliacoes.clone(),
+            diversidade: self.diversidade.clone(),
+            geracao: self.geracao,
         }
     }
 
+    #[allow(clippy::unnecessary_cast)]
     pub fn aptidao_media(&self) -> f64 {
         let total = self.individuos.iter().map(|i| i.aptidao).fold(0.0, |a, b| a + b) as f64;
         let qtd = self.individuos.len() as f64;
@@ -210,9 +323,88 @@ impl<Gene> Populacao<Gene> {
 
         soma_quadado_diff / (self.individuos.len() as f64 - 1.0)
     }
+
+    /// O desvio padrão das aptidões da população, a raiz quadrada de `desvio_aptidao`.
+    pub fn desvio_padrao_aptidao(&self) -> f64 {
+        self.desvio_aptidao().sqrt()
+    }
+
+    /// Média da distância entre todos os pares de indivíduos.
+    pub fn distancia_media<D>(&self, distancia: &D) -> f64
+        where D: Distancia<Gene>
+    {
+        distancia_media(&self.individuos, distancia)
+    }
+
+    /// Média da distância euclidiana dos indivíduos ao centróide da população.
+    pub fn distancia_centroide(&self) -> f64
+        where Gene: Posicoes
+    {
+        distancia_centroide(&self.individuos)
+    }
+
+    /// Média da entropia de cada posição do genoma.
+    pub fn entropia_genes(&self) -> f64
+        where Gene: Posicoes
+    {
+        entropia_genes(&self.individuos)
+    }
+
+    /// Passa a calcular a distância média com *distancia* em `diversidade`, inclusive nas
+    /// próximas gerações. Com essa configuração os observadores conseguem informar a
+    /// diversidade de cada geração.
+    pub fn medir_diversidade<D>(&mut self, distancia: D)
+        where D: Distancia<Gene> + Send + Sync + 'static
+    {
+        self.diversidade = Some(Arc::new(move |individuos: &[Individuo<Gene>]| {
+            Diversidade {
+                distancia_media: distancia_media(individuos, &distancia),
+                distancia_centroide: None,
+                entropia: None,
+            }
+        }));
+    }
+
+    /// Igual a `medir_diversidade`, calculando também a distância ao centróide e a entropia.
+    pub fn medir_diversidade_posicional<D>(&mut self, distancia: D)
+        where D: Distancia<Gene> + Send + Sync + 'static,
+              Gene: Posicoes
+    {
+        self.diversidade = Some(Arc::new(move |individuos: &[Individuo<Gene>]| {
+            Diversidade {
+                distancia_media: distancia_media(individuos, &distancia),
+                distancia_centroide: Some(distancia_centroide(individuos)),
+                entropia: Some(entropia_genes(individuos)),
+            }
+        }));
+    }
+
+    /// A diversidade da população, quando configurada com `medir_diversidade`.
+    pub fn diversidade(&self) -> Option<Diversidade> {
+        self.diversidade.as_ref().map(|medida| medida(&self.individuos))
+    }
+
+    /// A mediana das aptidões da população. Assim como na `aptidao_media`, uma população vazia
+    /// não tem mediana e o resultado é NaN.
+    pub fn mediana_aptidao(&self) -> f64 {
+        if self.individuos.is_empty() {
+            return f64::NAN;
+        }
+
+        let mut aptidoes: Vec<f64> = self.individuos.iter().map(|i| i.aptidao).collect();
+        aptidoes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let meio = aptidoes.len() / 2;
+        if aptidoes.len() % 2 == 0 {
+            (aptidoes[meio - 1] + aptidoes[meio]) / 2.0
+        } else {
+            aptidoes[meio]
+        }
+    }
 }
 
 
+#[allow(clippy::ptr_arg)]
 pub fn cria_populacao(entrada: &Vec<usize>, oper: Operacao) -> Populacao<usize> {
     let mut pop = Populacao::criar_vazia(AptidaoSimples, oper);
     for val in entrada.iter() {
@@ -265,12 +457,83 @@ mod test {
         valida_individuo_mais_apto(Operacao::Min, vec![1, 2, 3, 4], 1);
     }
 
+    #[test]
+    fn deve_calcular_desvio_e_mediana() {
+        let pop = cria_populacao(&vec![2, 4, 4, 4, 5, 5, 7, 9], Operacao::Max);
+        assert!((pop.desvio_aptidao() - 32.0 / 7.0).abs() < 0.00000001);
+        assert!((pop.desvio_padrao_aptidao() - (32.0f64 / 7.0).sqrt()).abs() < 0.00000001);
+        assert_eq!(4.5, pop.mediana_aptidao());
+        assert_eq!(4.0, cria_populacao(&vec![9, 1, 4], Operacao::Max).mediana_aptidao());
+        assert!(cria_populacao(&vec![], Operacao::Max).mediana_aptidao().is_nan());
+    }
+
+    #[test]
+    fn deve_medir_diversidade_nas_proximas_geracoes() {
+        let mut pop = cria_populacao(&vec![1, 2, 3], Operacao::Max);
+        assert!(pop.diversidade().is_none());
+
+        pop.medir_diversidade(DistanciaHamming);
+        let mut nova = pop.preparar_nova_geracao();
+        nova.adicionar(0b11);
+        nova.adicionar(0b00);
+
+        let diversidade = nova.diversidade().unwrap();
+        assert_eq!(2.0, diversidade.distancia_media);
+        assert!(diversidade.entropia.is_none());
+    }
+
     #[test]
     fn deve_mandater_individuos_mais_aptos() {
         valida_mais_apto(Operacao::Max, vec![1, 2, 3, 4], 2, vec![4, 3]);
         valida_mais_apto(Operacao::Min, vec![1, 2, 3, 4], 2, vec![1, 2]);
     }
 
+    #[test]
+    fn deve_adicionar_lote_em_paralelo() {
+        let mut pop = cria_populacao(&vec![], Operacao::Max);
+        pop.avaliacao_paralela(3);
+        pop.adicionar_lote(vec![5, 1, 4, 2, 3]);
+
+        assert_eq!(pop.individuos.len(), 5);
+        for ind in pop.individuos.iter() {
+            assert_eq!(ind.genes as f64, ind.aptidao);
+        }
+
+        let mut nova = pop.preparar_nova_geracao();
+        assert_eq!(nova.threads, 3);
+
+        // A contagem de avaliações continua na nova geração
+        nova.adicionar(6);
+        assert_eq!(pop.avaliacoes(), 6);
+
+        // A população inicial criada em paralelo é igual à criada em sequência
+        let paralela = Populacao::criar_paralela(AptidaoSimples,
+                                                 &CriadorTeste,
+                                                 50,
+                                                 Operacao::Max,
+                                                 4,
+                                                 &mut Aleatorio::semente(3));
+        let sequencial = Populacao::criar_com_aleatorio(AptidaoSimples,
+                                                        &CriadorTeste,
+                                                        50,
+                                                        Operacao::Max,
+                                                        &mut Aleatorio::semente(3));
+        let individuos = |pop: &Populacao<usize>| {
+            pop.individuos.iter().map(|ind| (ind.genes, ind.aptidao)).collect::<Vec<_>>()
+        };
+        assert_eq!(individuos(&paralela), individuos(&sequencial));
+        assert_eq!(50, paralela.avaliacoes());
+        assert_eq!(4, paralela.threads);
+    }
+
+    struct CriadorTeste;
+
+    impl CriadorIndividuos<usize> for CriadorTeste {
+        fn criar(&self, aleatorio: &mut Aleatorio) -> usize {
+            aleatorio.intervalo(0, 100)
+        }
+    }
+
     #[test]
     fn deve_comparar_correto() {
 
@@ -0,0 +1,99 @@
+//! # Resultado
+//!
+//! Informações sobre uma execução do AG: a melhor solução encontrada, quanto trabalho foi feito
+//! para encontrá-la e, opcionalmente, a evolução da aptidão ao longo das gerações.
+
+use std::time::Duration;
+
+use diversidade::Diversidade;
+use objetivo::Situacao;
+use populacao::{Individuo, Populacao};
+
+/// Estatísticas da aptidão da população em uma geração.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EstatisticaGeracao {
+    /// O número da geração, 0 é a população inicial
+    pub geracao: usize,
+    /// Melhor aptidão da geração
+    pub melhor: f64,
+    /// Aptidão média da geração
+    pub media: f64,
+    /// Pior aptidão da geração
+    pub pior: f64,
+    /// Desvio padrão da aptidão da geração, o mesmo valor da coluna `Coluna::Desvio` dos
+    /// observadores em arquivo
+    pub desvio: f64,
+    /// Quantidade de aptidões calculadas até essa geração
+    pub avaliacoes: usize,
+    /// Diversidade da geração, quando medida, veja `Populacao::medir_diversidade`
+    pub diversidade: Option<Diversidade>,
+}
+
+impl EstatisticaGeracao {
+    /// Calcula as estatísticas da população da geração informada.
+    pub fn calcular<Gene>(geracao: usize, pop: &Populacao<Gene>) -> Self {
+        EstatisticaGeracao {
+            geracao,
+            melhor: pop.melhor_aptidao(),
+            media: pop.aptidao_media(),
+            pior: pop.pior_aptidao(),
+            desvio: pop.desvio_padrao_aptidao(),
+            avaliacoes: pop.avaliacoes(),
+            diversidade: pop.diversidade(),
+        }
+    }
+
+    /// Calcula as estatísticas de todas as populações da situação, como as ilhas do
+    /// `ModeloIlhas`. A diversidade fica vazia, cada população mede apenas a sua.
+    pub fn calcular_situacao<Gene>(situacao: &Situacao<Gene>) -> Self {
+        let operacao = situacao.operacao();
+        let melhor = situacao.mais_apto.aptidao;
+        let qtd = situacao.individuos().count() as f64;
+        EstatisticaGeracao {
+            geracao: situacao.geracao,
+            melhor,
+            media: situacao.individuos().map(|i| i.aptidao).sum::<f64>() / qtd,
+            pior: situacao.individuos()
+                          .map(|i| i.aptidao)
+                          .fold(melhor, |a, b| operacao.menos_apto(a, b)),
+            desvio: situacao.desvio_padrao(),
+            avaliacoes: situacao.avaliacoes(),
+            diversidade: None,
+        }
+    }
+}
+
+/// O resultado de uma execução do AG.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Resultado<Gene> {
+    /// O indivíduo mais apto encontrado
+    pub melhor: Individuo<Gene>,
+    /// Quantidade de gerações executadas
+    pub geracoes: usize,
+    /// Quantidade de aptidões calculadas
+    pub avaliacoes: usize,
+    /// Descrição do objetivo que encerrou a execução, None quando a execução foi interrompida
+    /// antes do objetivo ser satisfeito
+    pub objetivo: Option<String>,
+    /// Tempo gasto executando as gerações
+    pub tempo: Duration,
+    /// Estatísticas de cada geração, quando habilitadas com `Genetico::registrar_historico` ou
+    /// `ModeloIlhas::registrar_historico`
+    pub historico: Option<Vec<EstatisticaGeracao>>,
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use populacao::{cria_populacao, Operacao};
+
+    #[test]
+    fn deve_calcular_o_desvio_padrao_da_geracao() {
+        let pop = cria_populacao(&vec![2, 4, 4, 4, 5, 5, 7, 9], Operacao::Max);
+        let estatistica = EstatisticaGeracao::calcular(3, &pop);
+        assert_eq!(9.0, estatistica.melhor);
+        assert_eq!(5.0, estatistica.media);
+        assert!((estatistica.desvio - (32.0f64 / 7.0).sqrt()).abs() < 0.00000001);
+    }
+}
@@ -0,0 +1,64 @@
+//! # Salvamento
+//!
+//! Estado completo de uma execução do AG. Com ele é possível parar uma execução longa e
+//! continuar depois exatamente do mesmo ponto, inclusive com a mesma sequência de números
+//! aleatórios.
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+use serde_json::Value;
+
+use aleatorio::Aleatorio;
+use populacao::Individuo;
+
+/// Tudo o que é necessário para continuar uma execução do AG.
+#[derive(Serialize, Deserialize)]
+pub struct EstadoGenetico<Gene> {
+    /// Quantidade de gerações já executadas
+    pub geracao: usize,
+    /// Indivíduos da população, com as suas aptidões
+    pub individuos: Vec<Individuo<Gene>>,
+    /// Estado do objetivo, veja `Objetivo::estado`
+    pub objetivo: Value,
+    /// Gerador de números aleatórios no ponto em que a execução parou
+    pub aleatorio: Aleatorio,
+    /// Quantidade de aptidões calculadas até o salvamento
+    #[serde(default)]
+    pub avaliacoes: usize,
+}
+
+impl<Gene> EstadoGenetico<Gene> {
+    /// Grava o estado em um arquivo JSON. O estado é gravado primeiro em `<caminho>.tmp`, que
+    /// só então substitui o arquivo, assim uma falha durante a gravação mantém o último estado
+    /// salvo.
+    pub fn salvar<P>(&self, caminho: P) -> io::Result<()>
+        where P: AsRef<Path>,
+              Gene: Serialize
+    {
+        let caminho = caminho.as_ref();
+        let mut temporario = caminho.as_os_str().to_owned();
+        temporario.push(".tmp");
+        let temporario = PathBuf::from(temporario);
+
+        let mut arquivo = BufWriter::new(File::create(&temporario)?);
+        serde_json::to_writer(&mut arquivo, self)?;
+        arquivo.into_inner()?.sync_all()?;
+        fs::rename(&temporario, caminho)
+    }
+
+    /// Lê um estado gravado com `salvar`.
+    pub fn carregar<P>(caminho: P) -> io::Result<Self>
+        where P: AsRef<Path>,
+              Gene: DeserializeOwned
+    {
+        let arquivo = BufReader::new(File::open(caminho)?);
+        Ok(serde_json::from_reader(arquivo)?)
+    }
+}
@@ -28,7 +28,7 @@ fn main() {
     // Preparando o modelo de evolução que estamos interessado
     let mut evolucao = EvolucaoMista::criar();
     evolucao.adicionar(Mutacao::criar(0.4, PosicoesRainhas));
-    evolucao.adicionar(Cruzamento::criar(SelecaoPorTorneio::criar(5),
+    evolucao.adicionar(Cruzamento::criar(SelecaoPorTorneio::criar(5).unwrap(),
                                          PosicoesRainhas,
                                          0.9));
 
@@ -40,7 +40,7 @@ fn main() {
 
     // Executando o algoritmo genetico
     let genetico = Genetico::criar(pop, evolucao, objetivo);
-    let solucao = genetico.buscar_solucao();
+    let solucao = genetico.buscar_solucao().melhor.genes;
     println!("\n\tSolução {:?}\n", solucao);
 
     println!("\t+---+---+---+---+---+---+---+---+");
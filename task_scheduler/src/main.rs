@@ -37,7 +37,7 @@ fn main() {
     evolucao.adicionar(Mutacao::criar(0.4, MutagenicoVetor{
     			estados: estados
     }));
-    evolucao.adicionar(Cruzamento::criar(SelecaoPorTorneio::criar(5),
+    evolucao.adicionar(Cruzamento::criar(SelecaoPorTorneio::criar(5).unwrap(),
                                          CruzadorVetor,
                                          0.9));
 
@@ -49,7 +49,7 @@ fn main() {
 
     // Executando o algoritmo genetico
     let genetico = Genetico::criar(pop, evolucao, objetivo);
-    let solucao = genetico.buscar_solucao();
+    let solucao = genetico.buscar_solucao().melhor.genes;
     
     println!("{:?}", tempos);
     println!("{:?}", solucao); 
@@ -16,13 +16,15 @@ POW=1
 
 #
 # Função de teste do percentual aplicado de mutação e seleção. Precisa que seja 
-# informado a função objetivo e se deve ser aplicado elitismo
+# informado a função objetivo e se deve ser aplicado elitismo. Quando o quarto
+# parametro é informado a mutação é aplicada por gene, com percentuais menores.
 #
 testa_mutacao() {
 
     FUNCAO=$1
     AGREGACAO=$2
     ELITISMO=$3
+    POR_GENE=$4
     
     SUFIX=""
     if [ "$ELITISMO" != "" ]
@@ -30,6 +32,14 @@ testa_mutacao() {
         ELITISMO="--elitismo"
         SUFIX="_elitismo"
     fi
+
+    PASSO_MUTACAO="5"
+    if [ "$POR_GENE" != "" ]
+    then
+        POR_GENE="--mutacao-por-gene"
+        PASSO_MUTACAO="0.5"
+        SUFIX="${SUFIX}_por_gene"
+    fi
     
     echo "============================"
     echo "     Função: $FUNCAO"
@@ -60,7 +70,7 @@ testa_mutacao() {
         		echo -n "  $i"
         	fi
         	
-            RESULTADO=`../target/release/trabalho $ELITISMO \
+            RESULTADO=`../target/release/trabalho $ELITISMO $POR_GENE \
                 --cruzamento $CRUZAMENTO \
                 --mutacao $QTD_MUTACAO  \
                 --geracoes $GERACOES \
@@ -71,7 +81,7 @@ testa_mutacao() {
             F=`echo $RESULTADO | cut -d';' -f 2 | awk '{printf("%.3f\n",$1)}' `
             echo "$vez;$QTD_MUTACAO;$QTD_CRUZAMENTO;$F" >> $DATA
         done
-        QTD_MUTACAO=`expr "$vez" "*" "5"`
+        QTD_MUTACAO=`awk "BEGIN { print $vez * $PASSO_MUTACAO }"`
     done
 
     R --vanilla -q << EOM
@@ -82,7 +92,7 @@ medias=aggregate(valores\$Rastrigin^(1/${POW}), list(valores\$Mutacao), ${AGREGA
 pdf("$PDF", width=7, height=4)
 plot(medias, type='l' , axes=FALSE, ann=FALSE, col='blue',
      panel.first = c(abline(h = 0, v = 0, col = "gray60"), grid(NA, 5, lwd = 2)))
-axis(1, at = seq(0, 100, by = 5))
+axis(1, at = seq(0, 20 * ${PASSO_MUTACAO}, by = ${PASSO_MUTACAO}))
 mtext("Mutação", side=1, line=3)
 axis(2)
 mtext("Menor Fitness", side=2, line=2)
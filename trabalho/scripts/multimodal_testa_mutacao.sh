@@ -9,5 +9,6 @@ testa_mutacao "multimodal_arranjo" "max" "elitismo"
 testa_mutacao "multimodal_arranjo" "max" ""
 testa_mutacao "multimodal_arranjo_binario" "max" "elitismo"
 testa_mutacao "multimodal_arranjo_binario" "max" ""
+testa_mutacao "multimodal_arranjo_binario" "max" "elitismo" "por-gene"
  
 
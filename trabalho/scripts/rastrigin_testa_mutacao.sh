@@ -7,5 +7,6 @@ compila
  
 
 testa_mutacao "rastrigin_binario" "min" "elitismo" 
-testa_mutacao "rastrigin_binario" "min" "" 
+testa_mutacao "rastrigin_binario" "min" ""
+testa_mutacao "rastrigin_binario" "min" "elitismo" "por-gene" 
 
@@ -7,14 +7,44 @@ use std::process;
 pub struct Configuracao {
     pub funcao: String, // Nome da função que será maximizada
     pub chance_mutacao: f64, // Percentual de mutacação
+    pub mutacao_por_gene: bool, // A chance de mutação é aplicada a cada gene
+    pub controle_mutacao: String, // Como a chance de mutação varia ao longo das gerações
     pub chance_cruzamento: f64, // Percentual de cruzamento
     pub modificador_cruzamento: String, // Modificador que pode ser aplicado ao cruzamento
+    pub pontos_cruzamento: usize, // Quantidade de cortes do cruzamento de k pontos
     pub geracoes: usize, // Quantidade máxima de gerações para o algoritmo genético
     pub seletor: String, // Tipo de seleção a ser aplicada no cruzamento
+    pub pressao_selecao: Option<f64>, // Parametro que controla a pressão do seletor
+    pub tamanho_torneio: usize, // Quantidade de participantes da seleção por torneio
     pub tamanho_populacao: usize, // Tamanho da população
     pub debug: u64, // Parametro que indica se deve ser logado informações de depuração
     pub print_solution: bool, // Parametro que informa se deve ser impresso o resultado encontrado
     pub elitismo: bool, // Ativa o elitismo
+    pub semente: Option<u64>, // Semente do gerador de números aleatórios
+    pub threads: usize, // Quantidade de threads usadas no cálculo da aptidão
+    pub log: Option<String>, // Prefixo dos arquivos de log da execução
+}
+
+impl Configuracao {
+    /// Os parametros da execução, gravados nos arquivos de log.
+    pub fn parametros(&self) -> Vec<(&'static str, String)> {
+        vec![("funcao", self.funcao.clone()),
+             ("mutacao", self.chance_mutacao.to_string()),
+             ("mutacao-por-gene", self.mutacao_por_gene.to_string()),
+             ("controle-mutacao", self.controle_mutacao.clone()),
+             ("cruzamento", self.chance_cruzamento.to_string()),
+             ("modificador-cruzamento", self.modificador_cruzamento.clone()),
+             ("pontos-cruzamento", self.pontos_cruzamento.to_string()),
+             ("geracoes", self.geracoes.to_string()),
+             ("seletor", self.seletor.clone()),
+             ("pressao-selecao",
+              self.pressao_selecao.map(|p| p.to_string()).unwrap_or_default()),
+             ("tamanho-torneio", self.tamanho_torneio.to_string()),
+             ("populacao", self.tamanho_populacao.to_string()),
+             ("elitismo", self.elitismo.to_string()),
+             ("semente", self.semente.map(|s| s.to_string()).unwrap_or_default()),
+             ("threads", self.threads.to_string())]
+    }
 }
 
 /// Processa os parametros de linha de comando.
@@ -23,23 +53,31 @@ pub fn ler() -> Option<Configuracao> {
     let parametros = prepara_parametros();
     if let Some(funcao) = parametros.value_of("funcao") {
 
-        let mutacao = to_int(parametros.value_of("mutacao").unwrap_or("0"));
+        let mutacao = to_float(parametros.value_of("mutacao").unwrap_or("0"));
         let cruzamento = to_int(parametros.value_of("cruzamento").unwrap_or("0"));
         let geracoes = to_int(parametros.value_of("geracoes").unwrap_or("0"));
 
         return Some(Configuracao {
             funcao: funcao.into(),
-            chance_mutacao: (mutacao as f64 / 100.0 as f64),
+            chance_mutacao: mutacao / 100.0,
+            mutacao_por_gene: parametros.occurrences_of("mutacao-por-gene") > 0,
+            controle_mutacao: parametros.value_of("controle-mutacao").unwrap_or("fixa").into(),
             chance_cruzamento: (cruzamento as f64 / 100.0 as f64),
             modificador_cruzamento: parametros.value_of("modificador-cruzamento")
                                               .unwrap()
                                               .into(),
+            pontos_cruzamento: to_int(parametros.value_of("pontos-cruzamento").unwrap_or("3")),
             tamanho_populacao: to_int(parametros.value_of("populacao").unwrap_or("100")),
             geracoes: geracoes,
             seletor: parametros.value_of("seletor").unwrap_or("torneio").into(),
+            pressao_selecao: parametros.value_of("pressao-selecao").map(to_float),
+            tamanho_torneio: to_int(parametros.value_of("tamanho-torneio").unwrap_or("5")),
             debug: parametros.occurrences_of("debug"),
             print_solution: parametros.occurrences_of("imprime-solucao") > 0,
             elitismo: parametros.occurrences_of("elitismo") > 0,
+            semente: parametros.value_of("semente").map(|semente| to_int(semente) as u64),
+            threads: to_int(parametros.value_of("threads").unwrap_or("1")),
+            log: parametros.value_of("log").map(|log| log.into()),
         });
     }
     return None;
@@ -59,7 +97,22 @@ fn prepara_parametros<'a>() -> ArgMatches<'a> {
                  .short("m")
                  .default_value("1")
                  .takes_value(true)
-                 .help("Ativa a mutação"))
+                 .help("Ativa a mutação. Percentual de indivíduos mutados ou, com \
+                        '--mutacao-por-gene', de genes mutados"))
+        .arg(Arg::with_name("mutacao-por-gene")
+                 .long("mutacao-por-gene")
+                 .takes_value(false)
+                 .help("Aplica a chance de mutação a cada gene de cada indivíduo, em vez de \
+                        mutar um único gene dos indivíduos sorteados"))
+        .arg(Arg::with_name("controle-mutacao")
+                 .long("controle-mutacao")
+                 .possible_values(&["fixa", "linear", "exponencial", "um-quinto"])
+                 .default_value("fixa")
+                 .takes_value(true)
+                 .help("Como a chance de mutação varia ao longo das gerações. No linear e no \
+                        exponencial a chance cai até um décimo do valor inicial na última \
+                        geração, no um-quinto aumenta quando mais de 1/5 dos mutantes supera \
+                        o original e diminui quando menos de 1/5 supera."))
         .arg(Arg::with_name("cruzamento")
                  .long("cruzamento")
                  .short("c")
@@ -69,10 +122,19 @@ fn prepara_parametros<'a>() -> ArgMatches<'a> {
         .arg(Arg::with_name("modificador-cruzamento")
                  .long("modificador-cruzamento")
                  .short("mc")
-                 .possible_values(&["um-ponto", "dois-pontos"])
+                 .possible_values(&["um-ponto",
+                                    "dois-pontos",
+                                    "k-pontos",
+                                    "uniforme",
+                                    "embaralhado"])
                  .default_value("um-ponto")
                  .takes_value(true)
                  .help("Aplica modificadores ao cruzamento"))
+        .arg(Arg::with_name("pontos-cruzamento")
+                 .long("pontos-cruzamento")
+                 .default_value("3")
+                 .takes_value(true)
+                 .help("Quantidade de cortes do cruzamento k-pontos"))
         .arg(Arg::with_name("geracoes")
                  .long("geracoes")
                  .short("g")
@@ -82,10 +144,29 @@ fn prepara_parametros<'a>() -> ArgMatches<'a> {
         .arg(Arg::with_name("seletor")
                  .long("seletor")
                  .short("s")
-                 .possible_values(&["torneio", "roleta"])
+                 .possible_values(&["torneio",
+                                    "roleta",
+                                    "sus",
+                                    "ranking-linear",
+                                    "ranking-exponencial",
+                                    "truncamento",
+                                    "boltzmann"])
                  .takes_value(true)
                  .default_value("torneio")
                  .help("Tipo de seleção usada para o cruzamento"))
+        .arg(Arg::with_name("pressao-selecao")
+                 .long("pressao-selecao")
+                 .takes_value(true)
+                 .help("Pressão de seleção do seletor. No ranking-linear é a pressão entre 1 e \
+                        2 (padrão 1.5), no ranking-exponencial a base entre 0 e 1 (padrão \
+                        0.9), no truncamento a fração selecionável da população (padrão 0.5) \
+                        e no boltzmann a temperatura inicial (padrão 100), que cai 5% a cada \
+                        geração."))
+        .arg(Arg::with_name("tamanho-torneio")
+                 .long("tamanho-torneio")
+                 .default_value("5")
+                 .takes_value(true)
+                 .help("Quantidade de participantes de cada torneio na seleção por torneio"))
         .arg(Arg::with_name("populacao")
                  .long("populacao")
                  .short("p")
@@ -115,6 +196,17 @@ fn prepara_parametros<'a>() -> ArgMatches<'a> {
                  .help("Ativa o elitismo.")
                  .short("e")
                  .takes_value(false))
+        .arg(Arg::with_name("semente")
+                 .long("semente")
+                 .help("Semente do gerador de números aleatórios. Execuções com a mesma \
+                        semente e os mesmos parametros encontram a mesma solução.")
+                 .takes_value(true))
+        .arg(Arg::with_name("threads")
+                 .long("threads")
+                 .short("t")
+                 .default_value("1")
+                 .takes_value(true)
+                 .help("Quantidade de threads usadas no cálculo da aptidão de cada geração"))
         .arg(Arg::with_name("log")
                  .long("log")
                  .help("Nome do arquivo a ser salvo o log do processamento. Serão gerados dois \
@@ -125,6 +217,19 @@ fn prepara_parametros<'a>() -> ArgMatches<'a> {
         .get_matches()
 }
 
+fn to_float(valor: &str) -> f64 {
+
+    use std::str::FromStr;
+
+    match FromStr::from_str(valor) {
+        Ok(val) => val,
+        Err(_) => {
+            println!("Valor numerico inválido {}", valor);
+            process::exit(-1);
+        }
+    }
+}
+
 fn to_int(valor: &str) -> usize {
 
     use std::str::FromStr;
@@ -36,13 +36,22 @@ use genetics::populacao::Populacao;
 // de uma estratégia de evolução, como por exemplo mutação/crossover/etc.
 use genetics::evolucao::EvolucaoMista;
 
-use genetics::evolucao::mutacao::Mutacao;
-use genetics::evolucao::cruzamento::Cruzamento;
-use genetics::evolucao::selecao::torneio::SelecaoPorTorneio;
-use genetics::evolucao::selecao::roleta::SeletorPorRoleta;
-use genetics::observador::ObservadorEvolucaoImprimeAptidao;
+use genetics::evolucao::mutacao::{Mutacao, MutacaoPorGene, ControleMutacao, DecaimentoLinear,
+                                  DecaimentoExponencial, RegraUmQuinto};
+use genetics::evolucao::cruzamento::{Cruzamento, Cruzador, CruzadorPontos, CruzadorUniforme,
+                                     CruzadorEmbaralhado};
+use genetics::evolucao::selecao::{Seletor, SelecaoPorTorneio, SeletorPorRoleta,
+                                  SelecaoUniversalEstocastica, SelecaoPorRanking,
+                                  SelecaoPorTruncamento, SelecaoBoltzmann};
+use genetics::observador::{ObservadorEvolucaoImprimeAptidao, ObservadorCsv, Coluna};
+use genetics::diversidade::DistanciaHamming;
 use genetics::objetivo::{ObjetivoMisto, NumeroMaximoIteracoes};
 use genetics::genetico::Genetico;
+use genetics::aleatorio::Aleatorio;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process;
 
 macro_rules! resolve {
     ($cfg: expr, $tipo: expr) => {
@@ -52,39 +61,62 @@ macro_rules! resolve {
 
             let mutagenico = $tipo;
             let aptidao = $tipo;
-            let cruzador = $tipo;
+            let cruzador = cruzador(&$cfg, $tipo);
             let criador = $tipo;
 
-            let populacao = Populacao::criar(aptidao, &criador,
-            		$cfg.tamanho_populacao, $tipo.operacao());
+            let aleatorio = match $cfg.semente {
+                Some(semente) => Aleatorio::semente(semente),
+                None => Aleatorio::criar(),
+            };
 
             let mut evolucao = EvolucaoMista::criar();
             if $cfg.chance_mutacao > 0.0 {
-                evolucao.adicionar(Mutacao::criar($cfg.chance_mutacao, mutagenico));
+                if $cfg.mutacao_por_gene {
+                    let mut mutacao = MutacaoPorGene::criar($cfg.chance_mutacao, mutagenico);
+                    if let Some(controle) = controle_mutacao(&$cfg) {
+                        mutacao.controle(controle);
+                    }
+                    evolucao.adicionar(mutacao);
+                } else {
+                    let mut mutacao = Mutacao::criar($cfg.chance_mutacao, mutagenico);
+                    if let Some(controle) = controle_mutacao(&$cfg) {
+                        mutacao.controle(controle);
+                    }
+                    evolucao.adicionar(mutacao);
+                }
             }
 
             if $cfg.chance_cruzamento > 0.0 {
-
-                if "torneio" == &*$cfg.seletor {
-                    evolucao.adicionar(Cruzamento::criar(SelecaoPorTorneio::criar(5),
-                                                         cruzador,
-                                                         $cfg.chance_cruzamento));
-                } else {
-                    evolucao.adicionar(Cruzamento::criar(SeletorPorRoleta::criar(),
-                                                         cruzador,
-                                                         $cfg.chance_cruzamento));
-                }
+                evolucao.adicionar(Cruzamento::criar(seletor(&$cfg),
+                                                     cruzador,
+                                                     $cfg.chance_cruzamento));
             }
 
             let mut objetivo = ObjetivoMisto::criar();
             objetivo.adicionar(NumeroMaximoIteracoes::criar($cfg.geracoes));
 
-            let mut genetico = Genetico::criar(populacao, evolucao, objetivo);
-            genetico.observador(ObservadorEvolucaoImprimeAptidao);
+            // A população inicial e as evoluções usam o mesmo gerador
+            let mut genetico = Genetico::criar_com_aleatorio(aleatorio, |aleatorio| {
+                let mut populacao = Populacao::criar_paralela(aptidao, &criador,
+                		$cfg.tamanho_populacao, $tipo.operacao(), $cfg.threads, aleatorio);
+                if $cfg.log.is_some() {
+                    populacao.medir_diversidade(DistanciaHamming);
+                }
+                populacao
+            }, evolucao, objetivo);
+            match $cfg.log {
+                Some(ref log) => genetico.observador(observador_log(&$cfg, log)),
+                None => genetico.observador(ObservadorEvolucaoImprimeAptidao),
+            }
             genetico.elitismo = $cfg.elitismo;
-            let sol = genetico.buscar_solucao();
+            let resultado = genetico.buscar_solucao();
+            info!("Aptidão {} em {} gerações, {} avaliações e {:?}",
+                  resultado.melhor.aptidao,
+                  resultado.geracoes,
+                  resultado.avaliacoes,
+                  resultado.tempo);
             if $cfg.print_solution {
-            	println!("{}", $tipo.format(sol));
+            	println!("{}", $tipo.format(resultado.melhor.genes));
             }
         }
     }
@@ -112,6 +144,94 @@ fn main() {
     }
 }
 
+/// Cria o seletor usado no cruzamento a partir do parametro '--seletor'.
+fn seletor<Gene>(cfg: &configuracao::Configuracao) -> Box<dyn Seletor<Gene> + Send + Sync> {
+    let pressao = cfg.pressao_selecao;
+    match &*cfg.seletor {
+        "torneio" => {
+            let torneio = SelecaoPorTorneio::criar(cfg.tamanho_torneio)
+                              .and_then(|torneio| {
+                                  torneio.validar(cfg.tamanho_populacao).map(|_| torneio)
+                              })
+                              .unwrap_or_else(|erro| {
+                                  println!("{}", erro);
+                                  process::exit(-1);
+                              });
+            Box::new(torneio)
+        }
+        "roleta" => Box::new(SeletorPorRoleta::criar()),
+        "sus" => Box::new(SelecaoUniversalEstocastica::criar()),
+        "ranking-linear" => Box::new(SelecaoPorRanking::criar_linear(pressao.unwrap_or(1.5))),
+        "ranking-exponencial" => {
+            Box::new(SelecaoPorRanking::criar_exponencial(pressao.unwrap_or(0.9)))
+        }
+        "truncamento" => Box::new(SelecaoPorTruncamento::criar(pressao.unwrap_or(0.5))),
+        "boltzmann" => Box::new(SelecaoBoltzmann::criar(pressao.unwrap_or(100.0), 0.95)),
+        _ => panic!("Seletor não mapeado: {}", cfg.seletor),
+    }
+}
+
+/// Cria o cruzador a partir do parametro '--modificador-cruzamento'. O cruzamento de um ponto
+/// usa o cruzador do próprio problema.
+fn cruzador<Gene, C>(cfg: &configuracao::Configuracao,
+                     problema: C)
+                     -> Box<dyn Cruzador<Gene> + Send + Sync>
+    where C: Cruzador<Gene> + Send + Sync + 'static,
+          CruzadorPontos: Cruzador<Gene>,
+          CruzadorUniforme: Cruzador<Gene>,
+          CruzadorEmbaralhado: Cruzador<Gene>
+{
+    match &*cfg.modificador_cruzamento {
+        "um-ponto" => Box::new(problema),
+        "dois-pontos" => Box::new(CruzadorPontos::criar(2)),
+        "k-pontos" => Box::new(CruzadorPontos::criar(cfg.pontos_cruzamento)),
+        "uniforme" => Box::new(CruzadorUniforme::criar(0.5)),
+        "embaralhado" => Box::new(CruzadorEmbaralhado),
+        _ => panic!("Cruzamento não mapeado: {}", cfg.modificador_cruzamento),
+    }
+}
+
+/// Cria o controle da chance de mutação a partir do parametro '--controle-mutacao'. Com a
+/// chance fixa não há controle.
+fn controle_mutacao(cfg: &configuracao::Configuracao)
+                    -> Option<Box<dyn ControleMutacao + Send + Sync>> {
+    let chance = cfg.chance_mutacao;
+    match &*cfg.controle_mutacao {
+        "fixa" => None,
+        "linear" => Some(Box::new(DecaimentoLinear::criar(chance, chance / 10.0, cfg.geracoes))),
+        "exponencial" => {
+            let fator = 0.1f64.powf(1.0 / cfg.geracoes.max(1) as f64);
+            Some(Box::new(DecaimentoExponencial::criar(chance, fator)))
+        }
+        "um-quinto" => Some(Box::new(RegraUmQuinto::criar(chance))),
+        _ => panic!("Controle de mutação não mapeado: {}", cfg.controle_mutacao),
+    }
+}
+
+/// Grava os parametros da execução em '<log>_parametros.txt' e cria o observador que grava a
+/// aptidão e a diversidade de cada geração em '<log>_fitness.csv'.
+fn observador_log(cfg: &configuracao::Configuracao,
+                  log: &str)
+                  -> ObservadorCsv<BufWriter<File>> {
+
+    let mut parametros = File::create(format!("{}_parametros.txt", log))
+                             .expect("Falha ao criar o arquivo de parametros");
+    for (nome, valor) in cfg.parametros() {
+        writeln!(parametros, "{}: {}", nome, valor).expect("Falha ao gravar os parametros");
+    }
+
+    let fitness = File::create(format!("{}_fitness.csv", log))
+                      .expect("Falha ao criar o arquivo de fitness");
+    let mut observador = ObservadorCsv::criar(BufWriter::new(fitness));
+    let mut colunas = Coluna::aptidao();
+    colunas.push(Coluna::DistanciaMedia);
+    observador.colunas(colunas);
+    for (nome, valor) in cfg.parametros() {
+        observador.parametro(nome, valor);
+    }
+    observador
+}
+
 /// Função que ativa o LOG, útil para compreender o que o algoritmo está fazendo por dentro.
 fn ativa_log(nivel: u64) {
     use simplelog::{TermLogger, CombinedLogger, LogLevelFilter};
@@ -2,7 +2,7 @@
 use configuracao::Configuracao;
 
 use genetics::aptidao::Aptidao;
-use genetics::evolucao::mutacao::Mutagenico;
+use genetics::evolucao::mutacao::{Mutagenico, MutagenicoGene};
 use genetics::evolucao::cruzamento::*;
 use genetics::aleatorio::Aleatorio;
 use genetics::populacao::{CriadorIndividuos, Operacao};
@@ -73,3 +73,17 @@ impl Mutagenico<[i32; 30]> for MultimodalArranjo {
         mutante
     }
 }
+
+impl MutagenicoGene<[i32; 30]> for MultimodalArranjo {
+    fn quantidade_genes(&self, _: &[i32; 30]) -> usize {
+        30
+    }
+
+    fn mutar_gene(&self,
+                  gene: &mut [i32; 30],
+                  posicao: usize,
+                  _: usize,
+                  aleatorio: &mut Aleatorio) {
+        gene[posicao] = aleatorio.intervalo(-100, 100);
+    }
+}
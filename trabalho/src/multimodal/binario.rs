@@ -2,46 +2,34 @@
 use configuracao::Configuracao;
 
 use genetics::aptidao::Aptidao;
-use genetics::evolucao::mutacao::Mutagenico;
+use genetics::evolucao::mutacao::{Mutagenico, MutagenicoGene, MutacaoBits};
 use genetics::evolucao::cruzamento::*;
 use genetics::aleatorio::Aleatorio;
 use genetics::populacao::{CriadorIndividuos, Operacao};
+use genetics::binario::{CadeiaBits, Decodificador, Codificacao};
 
 /// Classe do problema
 pub struct MultimodalBinario;
 
-pub fn map(valores: &[bool]) -> Vec<i32> {
-    let mut valores_mapeados = Vec::new();
-    let mut valor = 0;
-    for (idx, v) in valores.iter().enumerate() {
-
-        valor = valor << 1;
-        if *v {
-            valor = valor | 0b1;
-        }
-
-        let resto = (idx + 1) % 10;
-        if resto == 0 {
-            let valor_processado = (-500.0 + (valor as f64 / 1023.0) * 1000.0) as i32;
-
-            valores_mapeados.push(valor_processado);
-            valor = 0;
-        }
-    }
+/// Os 300 bits são 30 variáveis de 10 bits entre -500 e 500
+fn decodificador() -> Decodificador {
+    Decodificador::uniforme(30, 10, -500.0, 500.0, Codificacao::Binaria)
+}
 
-    valores_mapeados
+pub fn map(genes: &CadeiaBits) -> Vec<i32> {
+    decodificador().reais(genes).into_iter().map(|valor| valor as i32).collect()
 }
 
 
 impl MultimodalBinario {
-    pub fn format(&self, genes: [bool; 300]) -> String {
+    pub fn format(&self, genes: CadeiaBits) -> String {
 
         let valores = map(&genes);
         let mut texto = String::with_capacity(300);
         let valor = format!("f: {}", super::aptidao_unimodal(&*valores));
 
         texto.push_str(&*valor);
-        for (idx, gene) in genes.iter().enumerate() {
+        for (idx, gene) in genes.bits().enumerate() {
             let valorx = format!(" x_{}: {},", idx, gene);
             texto.push_str(&*valorx);
         }
@@ -57,63 +45,42 @@ impl MultimodalBinario {
 }
 
 /// Implementação que calcula a aptidão dos valores para o arranjo unimodal
-impl Aptidao<[bool; 300]> for MultimodalBinario {
-    fn calcular_aptidao(&self, genes: &[bool; 300]) -> f64 {
+impl Aptidao<CadeiaBits> for MultimodalBinario {
+    fn calcular_aptidao(&self, genes: &CadeiaBits) -> f64 {
         let valores = map(genes);
         super::aptidao_unimodal(&*valores)
     }
 }
 
-impl CriadorIndividuos<[bool; 300]> for MultimodalBinario {
-    fn criar(&self, aleatorio: &mut Aleatorio) -> [bool; 300] {
-        let mut genes = [false; 300];
-        for idx in 0..300 {
-            genes[idx] = aleatorio.chance() > 0.5;
-        }
-        genes
+impl CriadorIndividuos<CadeiaBits> for MultimodalBinario {
+    fn criar(&self, aleatorio: &mut Aleatorio) -> CadeiaBits {
+        CadeiaBits::aleatoria(decodificador().tamanho(), aleatorio)
     }
 }
 
-/// Cruzador de genes para a função de Rastrigin
-impl Cruzador<[bool; 300]> for MultimodalBinario {
+impl Cruzador<CadeiaBits> for MultimodalBinario {
     fn cruzar(&self,
               aleatorio: &mut Aleatorio,
-              primeiro: &[bool; 300],
-              segundo: &[bool; 300])
-              -> ([bool; 300], [bool; 300]) {
-        trace!("Cruzando UnimodalArranjo");
-        let idx_sorte = (aleatorio.chance() * 300.0) as usize;
-        let mut a = [false; 300];
-        let mut b = [false; 300];
-
-        for idx in 0..300 {
-
-            if idx < idx_sorte {
-                a[idx] = primeiro[idx];
-                b[idx] = segundo[idx];
-            } else {
-                a[idx] = segundo[idx];
-                b[idx] = primeiro[idx];
-            }
-        }
-
-        (a, b)
+              primeiro: &CadeiaBits,
+              segundo: &CadeiaBits)
+              -> (CadeiaBits, CadeiaBits) {
+        CruzadorPontos::criar(1).cruzar(aleatorio, primeiro, segundo)
     }
 }
 
-impl Mutagenico<[bool; 300]> for MultimodalBinario {
-    fn mutar(&self, gene: &[bool; 300], aleatorio: &mut Aleatorio) -> [bool; 300] {
-
-
-        let mut mutante = [false; 300];
-        for idx in 0..300 {
-            mutante[idx] = gene[idx];
-        }
+impl Mutagenico<CadeiaBits> for MultimodalBinario {
+    fn mutar(&self, gene: &CadeiaBits, aleatorio: &mut Aleatorio) -> CadeiaBits {
+        MutacaoBits::criar(1.0 / gene.len() as f64).mutar(gene, aleatorio)
+    }
+}
 
-        let indice = aleatorio.intervalo(0, 300);
-        mutante[indice] = aleatorio.chance() > 0.5;
+impl MutagenicoGene<CadeiaBits> for MultimodalBinario {
+    fn quantidade_genes(&self, gene: &CadeiaBits) -> usize {
+        gene.len()
+    }
 
-        mutante
+    fn mutar_gene(&self, gene: &mut CadeiaBits, posicao: usize, _: usize, _: &mut Aleatorio) {
+        gene.inverter(posicao);
     }
 }
 
@@ -127,27 +94,10 @@ mod test {
     #[test]
     fn deve_mapear() {
 
-        let vals = map(&[//
-                         true,
-                         true,
-                         true,
-                         true,
-                         true,
-                         true,
-                         true,
-                         true,
-                         true,
-                         true,
-                         false,
-                         false,
-                         false,
-                         false,
-                         false,
-                         false,
-                         false,
-                         false,
-                         false,
-                         false]);
+        let mut genes = CadeiaBits::criar(300);
+        genes.definir_segmento(0, 10, 0b11_1111_1111);
+        genes.definir_segmento(10, 10, 0);
+        let vals = map(&genes);
         assert_eq!(500, vals[0]);
         assert_eq!(-500, vals[1]);
     }
@@ -3,7 +3,7 @@
 use configuracao::Configuracao;
 
 use genetics::aptidao::Aptidao;
-use genetics::evolucao::mutacao::Mutagenico;
+use genetics::evolucao::mutacao::{Mutagenico, MutagenicoGene};
 use genetics::evolucao::cruzamento::*;
 use genetics::aleatorio::Aleatorio;
 use genetics::populacao::{CriadorIndividuos, Operacao};
@@ -62,3 +62,13 @@ impl Mutagenico<[f64; 2]> for RastriginArranjo {
         mutante
     }
 }
+
+impl MutagenicoGene<[f64; 2]> for RastriginArranjo {
+    fn quantidade_genes(&self, _: &[f64; 2]) -> usize {
+        2
+    }
+
+    fn mutar_gene(&self, gene: &mut [f64; 2], posicao: usize, _: usize, aleatorio: &mut Aleatorio) {
+        gene[posicao] = aleatorio.intervalo(-5.0, 5.0);
+    }
+}
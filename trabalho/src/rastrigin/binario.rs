@@ -2,7 +2,7 @@
 use configuracao::Configuracao;
 
 use genetics::aptidao::Aptidao;
-use genetics::evolucao::mutacao::Mutagenico;
+use genetics::evolucao::mutacao::{Mutagenico, MutagenicoGene};
 use genetics::evolucao::cruzamento::*;
 use genetics::aleatorio::Aleatorio;
 use genetics::populacao::{CriadorIndividuos, Operacao};
@@ -69,6 +69,16 @@ impl Mutagenico<u32> for RastriginBinario {
     }
 }
 
+impl MutagenicoGene<u32> for RastriginBinario {
+    fn quantidade_genes(&self, _: &u32) -> usize {
+        32
+    }
+
+    fn mutar_gene(&self, gene: &mut u32, posicao: usize, _: usize, _: &mut Aleatorio) {
+        *gene = trocar_bit(*gene, posicao);
+    }
+}
+
 /// Função que converte de
 pub fn binario_para_decimal(valor: u16) -> f64 {
 
@@ -2,7 +2,7 @@
 use configuracao::Configuracao;
 
 use genetics::aptidao::Aptidao;
-use genetics::evolucao::mutacao::Mutagenico;
+use genetics::evolucao::mutacao::{Mutagenico, MutagenicoGene};
 use genetics::evolucao::cruzamento::*;
 use genetics::aleatorio::Aleatorio;
 use genetics::populacao::{CriadorIndividuos, Operacao};
@@ -100,3 +100,17 @@ impl Mutagenico<[i32; 30]> for UnimodalArranjoDois {
         mutante
     }
 }
+
+impl MutagenicoGene<[i32; 30]> for UnimodalArranjoDois {
+    fn quantidade_genes(&self, _: &[i32; 30]) -> usize {
+        30
+    }
+
+    fn mutar_gene(&self,
+                  gene: &mut [i32; 30],
+                  posicao: usize,
+                  _: usize,
+                  aleatorio: &mut Aleatorio) {
+        gene[posicao] = aleatorio.intervalo(-100, 100);
+    }
+}